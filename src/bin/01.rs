@@ -1,4 +1,4 @@
-use std::collections::{hash_map, HashMap};
+use std::collections::HashMap;
 
 
 advent_of_code::solution!(1);
@@ -24,7 +24,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     left.sort();
     right.sort();
     let result = left.into_iter()
-        .zip(right.into_iter())
+        .zip(right)
         .map(|(l, r)|
             (l as i64 - r as i64).abs()
             ).sum::<i64>();
@@ -52,26 +52,9 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     let result = right.iter()
         .map(|r|
-            if m.contains_key(&r) { (m[&r] * r) as i64} else { 0 }
+            if m.contains_key(r) { (m[r] * r) as i64} else { 0 }
             ).sum::<i64>();
 
 
     Some(result as u32)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
-    }
-}
@@ -2,7 +2,7 @@ advent_of_code::solution!(2);
 use itertools::Itertools;
 
 
-fn is_decreasing(path: &Vec<i64>) -> bool {
+fn is_decreasing(path: &[i64]) -> bool {
     for i in 0..path.len() - 1 {
         if (path[i] > path[i+1]) && (path[i] - path[i + 1]) < 4 {
             continue;
@@ -13,7 +13,7 @@ fn is_decreasing(path: &Vec<i64>) -> bool {
     true
 }
 
-fn is_increasing(path: &Vec<i64>) -> bool {
+fn is_increasing(path: &[i64]) -> bool {
     for i in 0..path.len() - 1 {
         if (path[i] < path[i+1]) && (path[i + 1] - path[i]) < 4 {
             continue;
@@ -24,9 +24,9 @@ fn is_increasing(path: &Vec<i64>) -> bool {
     true
 }
 
-fn is_safe(path: &Vec<i64>) -> bool {
+fn is_safe(path: &[i64]) -> bool {
     if path.len() < 2 { true } else {
-        is_decreasing(&path) || is_increasing(&path)
+        is_decreasing(path) || is_increasing(path)
     }
 }
 
@@ -35,51 +35,71 @@ pub fn part_one(input: &str) -> Option<u32> {
         .map(|line|
             line.split_whitespace()
             .map(|num| num.parse::<i64>().unwrap()).collect::<Vec<i64>>())
-        .filter(|parsed_slope| is_safe(&parsed_slope))
+        .filter(|parsed_slope| is_safe(parsed_slope))
         .count();
     Some(result as u32)
 }
 
+// checks all levels in one pass, the first step decides the direction
+fn is_safe_in_one_pass<'a>(levels: impl Iterator<Item = &'a i64>) -> bool {
+    levels
+        .tuple_windows()
+        .try_fold(0, |ord, (a, b)| {
+            if ord >= 0 && (1..=3).contains(&(b - a)) {
+                Ok(1)
+            } else if ord <= 0 && (1..=3).contains(&(a - b)) {
+                Ok(-1)
+            } else {
+                Err(())
+            }
+        })
+        .is_ok()
+}
+
+fn is_safe_with_removal(nums: &[i64]) -> bool {
+    (0..nums.len()).any(|i| is_safe_in_one_pass(nums[0..i].iter().chain(&nums[i + 1..])))
+}
+
 pub fn part_two(input: &str) -> Option<u32> {
     let result:usize = input.lines()
         .map(|line|
             line.split_whitespace()
             .map(|num| num.parse::<i64>().unwrap()).collect::<Vec<i64>>())
-        .filter(|nums| {
-            (0..nums.len()).any(|i| {
-                nums[0..i]
-                    .iter()
-                    .chain(&nums[i + 1..])
-                    .tuple_windows()
-                    .try_fold(0, |ord, (a, b)| {
-                        if ord >= 0 && (1..=3).contains(&(b - a)) {
-                            Ok(1)
-                        } else if ord <= 0 && (1..=3).contains(&(a - b)) {
-                            Ok(-1)
-                        } else {
-                            Err(())
-                        }
-                    })
-                .is_ok()
-            })
-        }
-        ).count();
+        .filter(|nums| is_safe_with_removal(nums))
+        .count();
     Some(result as u32)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::util::prop::{self, Gen};
+
+    // reports of 1 to 8 levels that mostly change by a few steps, so both safe and unsafe reports are common
+    fn report() -> Gen<Vec<i64>> {
+        let levels = prop::vec(prop::int(1..=99), 1..=8);
+        Gen::new(
+            |rng| {
+                let mut level = rng.range(1..=99);
+                let len = rng.range(1..=8);
+                (0..len)
+                    .map(|_| {
+                        level += rng.range(-4..=4);
+                        level
+                    })
+                    .collect()
+            },
+            move |report| levels.shrink(report),
+        )
+    }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+    fn test_safety_checks_agree() {
+        prop::agree(&report(), |levels| is_safe(levels), |levels| is_safe_in_one_pass(levels.iter()));
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
+    fn test_safe_reports_stay_safe_with_removal() {
+        prop::check(&report(), |levels| !is_safe(levels) || is_safe_with_removal(levels));
     }
 }
@@ -1,58 +1,41 @@
-use std::collections::HashMap;
+use advent_of_code::util::grid::{Grid, Offset, Pos};
 
 advent_of_code::solution!(4);
 
-#[derive(Debug, PartialEq)]
-pub struct Point(i32, i32);
-
-static XMAS: &'static [[Point; 4]] = &[
-    [Point(0, 0), Point(1, -1), Point(2, -2), Point(3, -3),],
-    [Point(0, 0), Point(1, 0), Point(2, 0), Point(3, 0),],
-    [Point(0, 0), Point(1, 1), Point(2, 2), Point(3, 3),],
-    [Point(0, 0), Point(0, 1), Point(0, 2), Point(0, 3),],
+static XMAS: &[[Offset; 4]] = &[
+    [(0, 0), (1, -1), (2, -2), (3, -3),],
+    [(0, 0), (1, 0), (2, 0), (3, 0),],
+    [(0, 0), (1, 1), (2, 2), (3, 3),],
+    [(0, 0), (0, 1), (0, 2), (0, 3),],
 ];
 
 
-static X_MAS: &'static [[Point; 5]] = &[
+static X_MAS: &[[Offset; 5]] = &[
     [//start at top left
-        Point(0, 0),//top left - M or S
-        Point(2, 0),//top right - M or S
-        Point(1, 1),//center - A
-        Point(0, 2),//bot left -  M or S
-        Point(2, 2),//bot right - M or S
+        (0, 0),//top left - M or S
+        (0, 2),//top right - M or S
+        (1, 1),//center - A
+        (2, 0),//bot left -  M or S
+        (2, 2),//bot right - M or S
     ],
 ];
 
-fn vec_to_hashmap(data: &Vec<&str>) -> HashMap<(i32, i32), char> {
-    data.iter()
-        .enumerate()
-        .flat_map(|(row, line)| {
-            line.chars()
-                .enumerate()
-                .map(move |(col, ch)|
-                    ((row as i32, col as i32), ch)
-                )
-        })
+// letters at the given offsets from `pos`, `.` for the ones outside of the grid
+fn word_at(m: &Grid<char>, pos: Pos, offsets: &[Offset]) -> String {
+    offsets.iter()
+        .map(|&offset| m.offset(pos, offset).map_or('.', |p| m[p]))
         .collect()
 }
 
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let m = input.lines()
-        .collect::<Vec<_>>();
-
-    let m:HashMap<(i32, i32), char> = vec_to_hashmap(&m);
+    let m: Grid<char> = input.parse().unwrap();
 
-    let result:usize = m.keys()
-        .map(|(x, y)| {
+    let result:usize = m.positions()
+        .map(|pos| {
             XMAS.iter()
                 .filter(|p| {
-                    let word = &format!("{}{}{}{}",
-                        m.get(&(x + p[0].0, y + p[0].1)).unwrap_or(&'.'),
-                        m.get(&(x + p[1].0, y + p[1].1)).unwrap_or(&'.'),
-                        m.get(&(x + p[2].0, y + p[2].1)).unwrap_or(&'.'),
-                        m.get(&(x + p[3].0, y + p[3].1)).unwrap_or(&'.'),
-                    );
+                    let word = word_at(&m, pos, *p);
                     word == "XMAS" || word == "SAMX"
             }).count()
         }).sum();
@@ -61,42 +44,16 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let m = input.lines()
-        .collect::<Vec<_>>();
+    let m: Grid<char> = input.parse().unwrap();
 
-    let m:HashMap<(i32, i32), char> = vec_to_hashmap(&m);
-
-    let result:usize = m.keys()
-        .map(|(x, y)| {
+    let result:usize = m.positions()
+        .map(|pos| {
             X_MAS.iter()
                 .filter(|p| {
-                    let word = &format!("{}{}{}{}{}",
-                        m.get(&(x + p[0].0, y + p[0].1)).unwrap_or(&'.'),
-                        m.get(&(x + p[1].0, y + p[1].1)).unwrap_or(&'.'),
-                        m.get(&(x + p[2].0, y + p[2].1)).unwrap_or(&'.'),
-                        m.get(&(x + p[3].0, y + p[3].1)).unwrap_or(&'.'),
-                        m.get(&(x + p[4].0, y + p[4].1)).unwrap_or(&'.'),
-                    );
+                    let word = word_at(&m, pos, *p);
                     word == "MMASS" || word == "MSAMS" || word == "SMASM" || word == "SSAMM"
             }).count()
         }).sum();
 
     Some(result as u32)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(18));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(9));
-    }
-}
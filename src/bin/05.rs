@@ -1,113 +1,38 @@
-use std::{cmp::Ordering, collections::HashSet};
+use nom::{character::complete::{char, line_ending}, sequence::{pair, separated_pair}};
 
-use itertools::Itertools;
+use advent_of_code::util::order::Rules;
+use advent_of_code::util::parse::{comma_list, int, lines, parse_input, ParseError};
 
 advent_of_code::solution!(5);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let data = input.split_once("\n\n").unwrap();
-    let rules:HashSet<(&str, &str)> = data.0.lines()
-    //let rules:Vec<(&str, &str)> = data.0.lines()
-        .map(|l| {
-            let (f, s) = l.split_once('|').unwrap();
-            (f, s)
-        }
-        )
-        .collect();
-
-    let updates = data.1.lines()
-        .map(|l| l.split(',').collect_vec())
-        .collect_vec();
+// page ordering rules `47|53`, a blank line and the updates `75,47,61`
+fn parse(input: &str) -> Result<(Rules<u32>, Vec<Vec<u32>>), ParseError> {
+    let rules = lines(separated_pair(int, char('|'), int));
+    let updates = lines(comma_list(int));
+    let (rules, updates) = parse_input(input, separated_pair(rules, pair(line_ending, line_ending), updates))?;
 
-    //println!("LOGME: updates: {:?}", &updates);
-    let result:i32 = updates.iter()
-        .filter(|update| {
-            update.iter()
-                .tuple_windows()
-                .try_fold(1, |_is_ok, (l, r)|{
-                    //println!("LOGME: tuple: {:?} and {:?}", l, r);
-                    if rules.contains(&(r, l)) {
-                        Err(())
-                    } else {
-                        Ok(1)
-                    }
-                }).is_ok()
-        })
-        .map(|update| update.get(update.len()/2)
-            .unwrap()
-            .parse::<i32>()
-            .unwrap()
-        )
-        .sum();
-
-    //println!("LOGME: rules: {:?}", rules);
-    Some(result as u32)
+    Ok((rules.into_iter().collect(), updates))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let data = input.split_once("\n\n").unwrap();
-    let rules:HashSet<(&str, &str)> = data.0.lines()
-        .map(|l| {
-            let (f, s) = l.split_once('|').unwrap();
-            (f, s)
-        }
-        )
-        .collect();
-
-    let mut updates:Vec<Vec<_>> = data.1.lines()
-        .map(|l| l.split(',').collect_vec())
-        .collect_vec();
+pub fn part_one(input: &str) -> Option<u32> {
+    let (rules, updates) = parse(input).unwrap_or_else(|e| panic!("{e}"));
 
-    //println!("LOGME: updates: {:?}", &updates);
-    let result:i32 = updates.into_iter()
-        .filter(|update| {
-            update.iter()
-                .tuple_windows()
-                .try_fold(1, |_is_ok, (l, r)|{
-                    //println!("LOGME: tuple: {:?} and {:?}", l, r);
-                    if rules.contains(&(r, l)) {
-                        Err(())
-                    } else {
-                        Ok(1)
-                    }
-                }).is_err()
-        })
-        .map(|update| {
-            let mut sorted = update.clone();
-            sorted.sort_by(|&l, &r| {
-                if rules.contains(&(l, r)) {
-                    return Ordering::Less;
-                } else if rules.contains(&(r, l)) {
-                    return Ordering::Greater;
-                }
-                return Ordering::Equal;
-            });
-            sorted
-        })
-        .map(|update| update.get(update.len()/2)
-            .unwrap()
-            .parse::<i32>()
-            .unwrap()
-        )
+    let result:u32 = updates.iter()
+        .filter(|update| rules.is_ordered(update))
+        .map(|update| update[update.len()/2])
         .sum();
 
-    //println!("LOGME: rules: {:?}", rules);
-    Some(result as u32)
+    Some(result)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub fn part_two(input: &str) -> Option<u32> {
+    let (rules, updates) = parse(input).unwrap_or_else(|e| panic!("{e}"));
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
-    }
+    let result:u32 = updates.iter()
+        .filter(|update| !rules.is_ordered(update))
+        .map(|update| rules.sort(update).unwrap_or_else(|e| panic!("{e}")))
+        .map(|update| update[update.len()/2])
+        .sum();
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
-    }
+    Some(result)
 }
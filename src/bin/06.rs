@@ -1,140 +1,78 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
-use nom::AsChar;
+use advent_of_code::util::cycle;
+use advent_of_code::util::grid::{Grid, Pos};
+use advent_of_code::util::point::Direction;
+use advent_of_code::util::prop::Rng;
 
-advent_of_code::solution!(6);
-
-#[derive(Debug, PartialEq, Hash, Eq, Clone, Copy)]
-pub struct Point(i32, i32);
-
-impl Point {
-    pub fn add(self, other:&Point) -> Point {
-        Point(self.0 + other.0, self.1 + other.1)
-    }
-}
-
-static DIRS: &'static [Point; 4] =
-&[  Point(-1, 0),//up
-    Point(0, 1),//right
-    Point(1, 0),//down
-    Point(0, -1),//left
-];
-
-
-fn vec_to_hashmap(data: &Vec<&str>) -> HashMap<Point, char> {
-    data.iter()
-        .enumerate()
-        .flat_map(|(row, line)| {
-            line.chars()
-                .enumerate()
-                .map(move |(col, ch)|
-                    (Point(row as i32, col as i32), ch)
-                )
-        })
-        .collect()
-}
-
-fn turn(current_pos:usize) -> usize {
-    if current_pos == 3 { 0 } else { current_pos + 1 }
-}
+advent_of_code::solution!(6, generator = generate);
 
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let m = input.lines()
-        .collect::<Vec<_>>();
-
-    let mut m:HashMap<Point, char> = vec_to_hashmap(&m);
+    let mut m: Grid<char> = input.parse().unwrap();
 
-    let starting_pos: Point = m
-        .iter()
-        .find_map(|(&key, &val)| if val == '^' { Some(key) } else { None })
-        .unwrap();
+    let starting_pos = m.position(&'^').unwrap();
 
-    let mut pos = Some((starting_pos.clone(), 0));
-    while pos.is_some() {
-        pos = next(pos.unwrap(), &m, &Point(-1,-1));
-        if pos.is_some() {
-            m.insert(pos.unwrap().0, 'X');
-        }
+    let mut pos = (starting_pos, Direction::Up);
+    while let Some(next_pos) = next(pos, &m, None) {
+        m[next_pos.0] = 'X';
+        pos = next_pos;
     }
-    let result = m.values().filter(|v| *v == &'X'.as_char()).count();
+    let result = m.iter().filter(|(_, v)| **v == 'X').count();
 
     //since we have skipped point 0 we should add +1
     Some((result + 1) as u32)
 }
 
-fn next(iter:(Point, usize), m:&HashMap<Point, char>, obstacle_loc: &Point)
-    -> Option<(Point, usize)> {
+fn next(iter:(Pos, Direction), m:&Grid<char>, obstacle_loc: Option<Pos>)
+    -> Option<(Pos, Direction)> {
     let(loc, dir) = iter;
-    let next_pos = loc.add(&DIRS[dir]);
-    let next_loc = m.get(&next_pos).unwrap_or(&'E');
-    if &next_pos == obstacle_loc || next_loc == &'#' {
-        return Some((loc, turn(dir)));
-    } else if next_loc == &'E' {
-        return None;
+    let next_pos = m.offset(loc, dir.offset())?;
+    if Some(next_pos) == obstacle_loc || m[next_pos] == '#' {
+        return Some((loc, dir.turn_right()));
     }
-    return Some((next_pos, dir));
+    Some((next_pos, dir))
 }
 
-fn is_looped(m:&HashMap<Point, char>, start_pos:Point, obstacle_loc: &Point) -> bool {
-    let mut slow_pos = Some((start_pos.clone(), 0));
+fn is_looped(m:&Grid<char>, start_pos:Pos, obstacle_loc: Option<Pos>) -> bool {
+    cycle::brent((start_pos, Direction::Up), |&pos| next(pos, m, obstacle_loc)).is_some()
+}
 
-    let mut fast_pos = Some((start_pos.clone(), 0));
-    while fast_pos.is_some() && next(fast_pos.unwrap(), &m, obstacle_loc).is_some() {
-        slow_pos = next(slow_pos.unwrap(), m, obstacle_loc);
+// square maps with about one obstacle in 16 cells and a guard that leaves the map eventually
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = ((size as f64).sqrt() as usize).max(2);
+    loop {
+        let mut m = Grid::new(side, side, '.');
+        for pos in m.positions().collect::<Vec<_>>() {
+            if rng.range(0..=15) == 0 {
+                m[pos] = '#';
+            }
+        }
+        let guard = (rng.range(0..=side as i64 - 1) as usize, rng.range(0..=side as i64 - 1) as usize);
+        m[guard] = '^';
 
-        fast_pos = next(fast_pos.unwrap(), m, obstacle_loc);
-        fast_pos = next(fast_pos.unwrap(), m, obstacle_loc);
-        if slow_pos == fast_pos {
-            return true;
+        if !is_looped(&m, guard, None) {
+            return m.to_string();
         }
     }
-    return false;
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let m = input.lines()
-        .collect::<Vec<_>>();
-
-    let m:HashMap<Point, char> = vec_to_hashmap(&m);
-
-    let starting_pos: Point = m
-        .iter()
-        .find_map(|(&key, &val)| if val == '^' { Some(key) } else { None })
-        .unwrap();
-
-    let starting_point = starting_pos.clone();
-    let mut path:HashSet<Point> = HashSet::new();
-    let mut pos = Some((starting_pos.clone(), 0));
-    while pos.is_some() {
-        pos = next(pos.unwrap(), &m, &Point(-1,-1));
-        if pos.is_some() {
-            path.insert(pos.unwrap().0);
-        }
+    let m: Grid<char> = input.parse().unwrap();
+
+    let starting_point = m.position(&'^').unwrap();
+    let mut path:HashSet<Pos> = HashSet::new();
+    let mut pos = (starting_point, Direction::Up);
+    while let Some(next_pos) = next(pos, &m, None) {
+        path.insert(next_pos.0);
+        pos = next_pos;
     }
     let result = path.iter()
-        .filter(|&obstacle_loc| {
-            obstacle_loc != &starting_point && is_looped(&m, starting_point, obstacle_loc)
+        .filter(|&&obstacle_loc| {
+            obstacle_loc != starting_point && is_looped(&m, starting_point, Some(obstacle_loc))
         })
         .count();
 
 
     Some(result as u32)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(42));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7));
-    }
-}
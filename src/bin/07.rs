@@ -1,7 +1,14 @@
 use itertools::Itertools;
 
+use advent_of_code::util::parse::{int, key_values, lines, parse_input, ws_list, ParseError};
+
 advent_of_code::solution!(7);
 
+// equations like `190: 10 19`
+fn parse(input: &str) -> Result<Vec<(i64, Vec<i64>)>, ParseError> {
+    parse_input(input, lines(key_values(int, ws_list(int))))
+}
+
 fn evaluate_expression(numbers: &[i64], operators: &[i64]) -> Option<i64> {
     if numbers.len() != operators.len() + 1 {
         return None;
@@ -22,16 +29,8 @@ fn evaluate_expression(numbers: &[i64], operators: &[i64]) -> Option<i64> {
 
 
 pub fn part_one(input: &str) -> Option<i64> {
-    let result:i64 = input.lines()
-        .map(|l| {
-            let(y,  operands) = l.split_once(':').unwrap();
-
-            (y.parse::<i64>().unwrap(), operands
-             .split_whitespace()
-             .map(|o| o.trim())
-             .map(|o| o.parse::<i64>().unwrap())
-             .collect_vec())
-        })
+    let result:i64 = parse(input).unwrap_or_else(|e| panic!("{e}"))
+        .into_iter()
         .filter(|(y, operands)| {
             let operator_num:usize = operands.len() - 1;
             let possible_options = (0..operator_num).map(|_| 0..2).multi_cartesian_product().collect_vec();
@@ -42,20 +41,12 @@ pub fn part_one(input: &str) -> Option<i64> {
         .map(|(y, _operands)| y)
         .sum();
 
-    Some(result as i64)
+    Some(result)
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let result:i64 = input.lines()
-        .map(|l| {
-            let(y,  operands) = l.split_once(':').unwrap();
-
-            (y.parse::<i64>().unwrap(), operands
-             .split_whitespace()
-             .map(|o| o.trim())
-             .map(|o| o.parse::<i64>().unwrap())
-             .collect_vec())
-        })
+    let result:i64 = parse(input).unwrap_or_else(|e| panic!("{e}"))
+        .into_iter()
         .filter(|(y, operands)| {
             let operator_num:usize = operands.len() - 1;
             let possible_options = (0..operator_num).map(|_| 0..3).multi_cartesian_product().collect_vec();
@@ -66,31 +57,6 @@ pub fn part_two(input: &str) -> Option<i64> {
         .map(|(y, _operands)| y)
         .sum();
 
-    Some(result as i64)
-
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
-    }
-
-    #[test]
-    fn test_permu() {
-        let k = 2;
-        let possible_options = (0..1).map(|_| 0..2).multi_cartesian_product().collect_vec();
-
-        println!("LOMGE: operands: {:?}, options: {:?}", &k, &possible_options);
-    }
+    Some(result)
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
-    }
 }
@@ -2,7 +2,9 @@ use std::cmp;
 
 use itertools::Itertools;
 
-advent_of_code::solution!(9);
+use advent_of_code::util::prop::Rng;
+
+advent_of_code::solution!(9, generator = generate);
 
 /// [Triangular numbers](https://en.wikipedia.org/wiki/Triangular_number) offset by two.
 /// When evaluating n+1 elements  we can use triangular numbers, and hand code them as memoization:
@@ -14,6 +16,15 @@ fn eval_sum(checksum: usize, block_id: usize, index: usize, size: usize) -> (usi
     (checksum + id * extra, block_id + size)
 }
 
+// disk maps of alternating file and free space lengths that end with a file, `size` digits long
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let digits = (0..size.max(1) | 1)
+        .map(|i| if i % 2 == 0 { rng.range(1..=9) } else { rng.range(0..=9) })
+        .map(|digit| char::from_digit(digit as u32, 10).unwrap())
+        .collect::<String>();
+    digits + "\n"
+}
+
 pub fn part_one(input: &str) -> Option<usize> {
     let input = input.trim().chars()
         .map(|ch| ch.to_digit(10).unwrap())
@@ -77,9 +88,9 @@ pub fn part_two(input: &str) -> Option<i64> {
     }
 
     // Add sentinel value and reverse vecs so that smallest blocks are last.
-    for i in 0..10 {
-        free[i].push(block);
-        free[i].reverse();
+    for blocks in &mut free {
+        blocks.push(block);
+        blocks.reverse();
     }
 
     for (index, &size) in disk.iter().enumerate().rev() {
@@ -94,9 +105,9 @@ pub fn part_two(input: &str) -> Option<i64> {
         let mut next_block = block;
         let mut next_index = usize::MAX;
 
-        for i in size..free.len() {
-            let top = free[i].len() - 1;
-            let first = free[i][top];
+        for (i, blocks) in free.iter().enumerate().skip(size) {
+            let top = blocks.len() - 1;
+            let first = blocks[top];
 
             if first < next_block {
                 next_block = first;
@@ -144,25 +155,3 @@ pub fn part_two(input: &str) -> Option<i64> {
 
     Some(checksum as i64)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1928));
-    }
-    #[test]
-    fn check_len() {
-        let input = "2333133121414131402";
-        assert_eq!(9, input.len() / 2);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2858));
-    }
-}
@@ -1,8 +1,11 @@
-use std::{collections::HashMap, mem::swap};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 
-advent_of_code::solution!(11);
+use advent_of_code::util::math;
+use advent_of_code::util::prop::Rng;
+
+advent_of_code::solution!(11, generator = generate);
 
 fn process_number(num:usize) -> Vec<usize> {
     match num {
@@ -22,13 +25,10 @@ fn process_number(num:usize) -> Vec<usize> {
 }
 
 fn solve_brute(data: &[usize], blink_number:usize) -> usize {
-    let mut current:Vec<usize> = Vec::with_capacity(5000);
-    current = data.to_vec().clone();
-    let mut next:Vec<usize> = Vec::with_capacity(5000);
+    let mut current:Vec<usize> = data.to_vec();
 
     for _ in  0..blink_number {
-        next = current.iter().flat_map(|num| process_number(num.clone())).collect_vec();
-        swap(&mut current, &mut next);
+        current = current.iter().flat_map(|&num| process_number(num)).collect_vec();
     }
     current.len()
 }
@@ -37,7 +37,7 @@ fn dfs_count(num:usize, blink_remaining:usize, memo:&mut HashMap<(usize, usize),
     if memo.contains_key(&(num, blink_remaining)) {
         memo[&(num, blink_remaining)]
     } else if blink_remaining == 0 {
-        return 1;
+        1
     } else {
         let result = match num {
             0 => dfs_count(1, blink_remaining - 1, memo),
@@ -60,13 +60,15 @@ fn dfs_count(num:usize, blink_remaining:usize, memo:&mut HashMap<(usize, usize),
 
 fn solve_dfs(data: &[usize], blink_number:usize) -> usize {
     let mut memo:HashMap<(usize, usize), u64> = HashMap::new();
-    data.into_iter()
+    data.iter()
         .fold(0, |acc, num| {
             let res = dfs_count(*num, blink_number, &mut memo) as usize;
             acc + res
         }
         )
 }
+// only the tests compare it with the other counters, the parts use `solve_dfs`
+#[cfg(test)]
 fn solve_like_master_tought_me(data: Vec<usize>, iterations: usize) -> u64 {
     // Initialize frequency map
     let mut map: HashMap<usize, u64> = HashMap::new();
@@ -99,29 +101,24 @@ fn solve_like_master_tought_me(data: Vec<usize>, iterations: usize) -> u64 {
 
 // Check if a number has even number of digits
 fn even_digits(number: usize) -> bool {
-    digits_len(number) % 2 == 0
-}
-
-// Calculate number of digits in a number
-fn digits_len(mut number: usize) -> usize {
-    if number == 0 {
-        return 1;
-    }
-    let mut len = 0;
-    while number > 0 {
-        len += 1;
-        number /= 10;
-    }
-    len
+    math::digit_count(number).is_multiple_of(2)
 }
 
 // Split the number into two halves
 fn split_number(number: usize) -> (usize, usize) {
-    let len = digits_len(number);
-    let half_len = len / 2;
-    let divisor = 10_i32.pow(half_len as u32) as usize;
+    math::split_digits(number, math::digit_count(number) / 2)
+}
 
-    (number / divisor, number % divisor)
+// stones with up to six digits, like the real input
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut stones = vec![];
+    let mut len = 0;
+    while len < size.max(1) {
+        let stone = rng.range(0..=999_999).to_string();
+        len += stone.len() + 1;
+        stones.push(stone);
+    }
+    stones.join(" ") + "\n"
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -139,11 +136,11 @@ pub fn part_two(input: &str) -> Option<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::util::prop::{self, Gen};
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
+    // a few stones and a number of blinks that the brute force counter handles quickly
+    fn stones_and_blinks() -> Gen<(Vec<usize>, usize)> {
+        prop::zip(prop::vec(prop::int(0..=1_000_000), 1..=4), prop::int(0..=15))
     }
 
     #[test]
@@ -157,8 +154,25 @@ mod tests {
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(65601038650482));
+    fn test_stone_counters_agree() {
+        let gen = stones_and_blinks();
+        prop::agree(&gen, |(stones, blinks)| solve_brute(stones, *blinks), |(stones, blinks)| solve_dfs(stones, *blinks));
+        prop::agree(&gen, |(stones, blinks)| solve_dfs(stones, *blinks) as u64, |(stones, blinks)| solve_like_master_tought_me(stones.clone(), *blinks));
+    }
+
+    #[test]
+    fn test_number_splits_agree() {
+        prop::agree(
+            &prop::int(0..=1_000_000_000_000usize),
+            |num| process_number(*num),
+            |num| match num {
+                0 => vec![1],
+                n if even_digits(*n) => {
+                    let (left, right) = split_number(*n);
+                    vec![left, right]
+                }
+                n => vec![n * 2024],
+            },
+        );
     }
 }
@@ -1,61 +1,16 @@
-use std::{cmp::min, num::ParseIntError, ops::{Add, Mul, Sub}, str::FromStr};
+use nom::{
+    character::complete::{anychar, line_ending},
+    combinator::map,
+    sequence::{preceded, separated_pair, terminated, tuple},
+};
 
-advent_of_code::solution!(13);
-
-#[derive(Debug, PartialEq, Hash, Eq, Clone, Copy, PartialOrd, Ord)]
-pub struct Point(i64, i64);
-
-
-impl Point {
-    pub fn add(self, other:&Point) -> Point {
-        Point(self.0 + other.0, self.1 + other.1)
-    }
-    pub fn to_idx(self, width:usize) -> usize {
-        self.1 as usize * width + self.0 as usize
-    }
-}
-
-impl Sub for Point {
-    type Output = Point;
-
-    fn sub(self, other:Point) -> Point {
-        Point(self.0 - other.0, self.1 - other.1)
-    }
-}
-
-impl Add for Point {
-    type Output = Point;
-
-    fn add(self, other:Point) -> Point {
-        Point(self.0 + other.0, self.1 + other.1)
-    }
-}
-impl Add<i64> for Point {
-    type Output = Point;
-
-    fn add(self, other:i64) -> Point {
-        Point(self.0 + other, self.1 + other)
-    }
-}
-
-impl Mul for Point {
-    type Output = Point;
-
-    fn mul(self, other:Point) -> Point {
-        Point(self.0 * other.0, self.1 * other.1)
-    }
+use advent_of_code::util::parse::{blocks, int, literal, parse_input, IResult, ParseError};
+use advent_of_code::util::math;
+use advent_of_code::util::point::Vec2;
 
-}
-
-impl Mul<i64> for Point {
-    type Output = Point;
-
-    fn mul(self, other:i64) -> Point {
-        Point(self.0 * other, self.1 * other)
-    }
-
-}
+advent_of_code::solution!(13);
 
+type Point = Vec2<i64>;
 
 #[derive(Debug, PartialEq)]
 pub struct Game {
@@ -65,61 +20,65 @@ pub struct Game {
 }
 
 impl Game {
-    fn parse_button(btn:&str) -> Result<Point, ParseIntError> {
-        let (_, coords) = btn.split_once(':').unwrap();
-        let (x, y) = coords.split_once(',').unwrap();
-
-        Ok(
-            Point(
-                x.trim().strip_prefix("X+").unwrap().parse()?,
-                y.trim().strip_prefix("Y+").unwrap().parse()?,
-            )
-        )
+    // `Button A: X+94, Y+34`
+    fn parse_button(btn:&str) -> IResult<'_, Point> {
+        preceded(
+            tuple((literal("Button "), anychar, literal(": X+"))),
+            map(separated_pair(int, literal(", Y+"), int), |(x, y)| Point::new(x, y)),
+        )(btn)
     }
-    fn parse_prize(btn:&str) -> Result<Point, ParseIntError> {
-        let (_, coords) = btn.split_once(':').unwrap();
-        let (x, y) = coords.split_once(',').unwrap();
-
-        Ok(
-            Point(
-                x.trim().strip_prefix("X=").unwrap().parse()?,
-                y.trim().strip_prefix("Y=").unwrap().parse()?,
-            )
-        )
+
+    // `Prize: X=8400, Y=5400`
+    fn parse_prize(prize:&str) -> IResult<'_, Point> {
+        preceded(
+            literal("Prize: X="),
+            map(separated_pair(int, literal(", Y="), int), |(x, y)| Point::new(x, y)),
+        )(prize)
+    }
+
+    fn parse(data:&str) -> IResult<'_, Game> {
+        map(
+            tuple((
+                terminated(Game::parse_button, line_ending),
+                terminated(Game::parse_button, line_ending),
+                Game::parse_prize,
+            )),
+            |(a, b, prize)| Game { a, b, prize },
+        )(data)
     }
 
     pub fn cost(p:&Point) -> i64 {
-        p.0*3 + p.1*1
+        p.x*3 + p.y
     }
 
     pub fn solve_brute(&self, multi:i64) -> Option<Point> {
-        let mut best_value = i64::max_value();
-        let mut best_actions = Point(0, 0);
-        let press_a = Point(1, 0);
-        let press_b = Point(0, 1);
-        let prize = self.prize + multi;
-
-        let mut cur_point = Point(0, 0);
-        while self.a * cur_point.0 < prize {
-            while self.b * cur_point.1 < prize {
-                let cur_point_value = (self.a * cur_point.0) + (self.b * cur_point.1);
-                if  cur_point_value == prize {
-                    if best_value > Game::cost(&cur_point_value) {
-                        best_value = Game::cost(&cur_point_value);
-                        best_actions = cur_point.clone();
-                    }
+        let mut best: Option<Point> = None;
+        let press_a = Point::new(1, 0);
+        let press_b = Point::new(0, 1);
+        let prize = self.prize + Point::splat(multi);
+        // the derived ordering of points is lexicographic, so compare each coordinate
+        let within = |p: Point| p.x <= prize.x && p.y <= prize.y;
+        let position = |presses: Point| self.a * presses.x + self.b * presses.y;
+
+        let mut cur_point = Point::new(0, 0);
+        while within(position(cur_point)) {
+            while within(position(cur_point)) {
+                if position(cur_point) == prize
+                    && best.is_none_or(|best| Game::cost(&cur_point) < Game::cost(&best))
+                {
+                    best = Some(cur_point);
                 }
-                cur_point = cur_point + press_b;
+                cur_point += press_b;
             }
             //reset button b
-            cur_point = Point(cur_point.0, 0);
-            cur_point = cur_point + press_a;
+            cur_point = Point::new(cur_point.x, 0);
+            cur_point += press_a;
         }
-        Some(best_actions)
+        best
     }
 
     pub fn solve_equation(&self, multi:i64) -> Option<Point> {
-        let prize = self.prize + multi;
+        let prize = self.prize + Point::splat(multi);
         /*
          T.x = press_a * A.x + press_b * B.x
          T.y = press_a * A.y + press_b * B.y
@@ -132,8 +91,11 @@ impl Game {
          (B.x * T.y - B.y * T.x) / (Ay * Bx - Ax * By) = press_a
 
          */
-        let press_a:f64 = (self.b.0 * prize.1 - self.b.1 * prize.0) as f64
-            / (self.a.1 * self.b.0 - self.a.0 * self.b.1) as f64;
+        if self.a.x * self.b.y == self.a.y * self.b.x {
+            return self.solve_collinear(prize);
+        }
+        let press_a:f64 = (self.b.x * prize.y - self.b.y * prize.x) as f64
+            / (self.a.y * self.b.x - self.a.x * self.b.y) as f64;
         //println!("press a: {}", press_a);
         //// Find pressB
         // T.x = pressA * A.x + pressB * B.x
@@ -143,7 +105,7 @@ impl Game {
             //println!("-- press a fraction is : {}", press_a.fract());
             return None
         }
-        let press_b = (prize.0 as f64 - press_a * self.a.0 as f64) / self.b.0 as f64;
+        let press_b = (prize.x as f64 - press_a * self.a.x as f64) / self.b.x as f64;
 
         // println!("press b: {}", press_b);
         if press_b.fract() != 0f64 {
@@ -151,31 +113,52 @@ impl Game {
             return None
         }
 
-        Some(Point(press_a as i64, press_b as i64))
-    }
-
-}
+        //buttons cannot be pressed a negative number of times
+        if press_a < 0f64 || press_b < 0f64 {
+            return None
+        }
 
-impl FromStr for Game {
-    type Err = std::num::ParseIntError;
+        Some(Point::new(press_a as i64, press_b as i64))
+    }
 
+    // Both buttons move in the same direction, so the prize has to lie on that line too and
+    // `press_a * A.x + press_b * B.x = T.x` has many solutions. Replacing `A.x / g` presses of
+    // A with `B.x / g` presses of B changes the cost by `(3 * B.x - A.x) / g`, so the cheapest
+    // solution presses one of the buttons as few times as possible (A on a tie, like the brute force).
+    fn solve_collinear(&self, prize: Point) -> Option<Point> {
+        let (a, b) = (self.a.x, self.b.x);
+        if a == 0 || b == 0 || self.a.x * prize.y != self.a.y * prize.x {
+            return None
+        }
+        let (g, x, y) = math::egcd(a, b);
+        if prize.x % g != 0 {
+            return None
+        }
 
-    fn from_str(data: &str) -> Result<Self, Self::Err> {
-        let mut it = data.splitn(3, '\n');
+        let (press_a, press_b) = if 3 * b >= a {
+            let press_a = (x * (prize.x / g)).rem_euclid(b / g);
+            (press_a, (prize.x - press_a * a) / b)
+        } else {
+            let press_b = (y * (prize.x / g)).rem_euclid(a / g);
+            ((prize.x - press_b * b) / a, press_b)
+        };
 
-        Ok(Game {
-            a: Game::parse_button(it.next().unwrap())?,
-            b: Game::parse_button(it.next().unwrap())?,
-            prize: Game::parse_prize(it.next().unwrap())?,
-        })
+        //buttons cannot be pressed a negative number of times
+        if press_a < 0 || press_b < 0 {
+            return None
+        }
+        Some(Point::new(press_a, press_b))
     }
 
 }
 
+fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    parse_input(input, blocks(Game::parse))
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    let res:i64 = input
-        .split("\n\n")
-        .map(|game| game.parse::<Game>().unwrap())
+    let res:i64 = parse(input).unwrap_or_else(|e| panic!("{e}"))
+        .into_iter()
         //.map(|game| game.solve_brute(0))
         .map(|game| game.solve_equation(0))
         .filter(|game| game.is_some())
@@ -186,30 +169,65 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let res:i64 = input
-        .split("\n\n")
-        .map(|game| game.parse::<Game>().unwrap())
+    let res:i64 = parse(input).unwrap_or_else(|e| panic!("{e}"))
+        .into_iter()
         .map(|game| game.solve_equation(10000000000000))
         .filter(|game| game.is_some())
         .map(|p| Game::cost(&p.unwrap()))
         .sum();
 
-    Some(res as i64)
+    Some(res)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::util::prop::{self, Gen};
+
+    type Coords = (i64, i64);
+
+    // two buttons, presses of both buttons and an offset that may make the prize unreachable
+    fn game() -> Gen<((Coords, Coords), (Coords, Coords))> {
+        let button = || prop::zip(prop::int(5..=30), prop::int(5..=30));
+        let buttons = prop::zip(button(), button());
+        let presses = prop::zip(prop::int(0..=10), prop::int(0..=10));
+        let offset = prop::zip(prop::int(0..=3), prop::int(0..=3));
+
+        prop::zip(buttons, prop::zip(presses, offset))
+    }
+
+    fn build(&((a, b), (presses, offset)): &((Coords, Coords), (Coords, Coords))) -> Game {
+        let (a, b) = (Point::new(a.0, a.1), Point::new(b.0, b.1));
+        Game {
+            a,
+            b,
+            prize: a * presses.0 + b * presses.1 + Point::new(offset.0, offset.1),
+        }
+    }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(480));
+    fn test_solvers_agree() {
+        prop::agree(
+            &game(),
+            |game| build(game).solve_brute(0),
+            |game| build(game).solve_equation(0),
+        );
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part("examples", DAY, 2));
-        assert_eq!(result, None);
+    fn test_collinear_buttons() {
+        // random buttons are rarely collinear, so try all small multiples of a direction,
+        // including ties like A = 3 * B
+        let direction = Point::new(2, 3);
+        for a in 1..=7 {
+            for b in 1..=7 {
+                for t in 0..=60 {
+                    let game = Game { a: direction * a, b: direction * b, prize: direction * t };
+                    assert_eq!(game.solve_brute(0), game.solve_equation(0), "{game:?}");
+                }
+                let game = Game { a: direction * a, b: direction * b, prize: Point::new(2, 4) };
+                assert_eq!(game.solve_equation(0), None);
+            }
+        }
     }
 }
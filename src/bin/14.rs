@@ -1,68 +1,30 @@
-use std::{collections::HashSet, num::ParseIntError, ops::{Add, Mul, Sub}, str::FromStr};
+use std::collections::HashSet;
 
-use itertools::Itertools;
+use nom::{
+    character::complete::{char, space1},
+    combinator::map,
+    sequence::{preceded, separated_pair},
+};
 
-advent_of_code::solution!(14);
+use advent_of_code::template::examples::param;
+use advent_of_code::util::math;
+use advent_of_code::util::parse::{int, lines, literal, parse_input, IResult};
+use advent_of_code::util::point::Vec2;
+use advent_of_code::util::prop::Rng;
 
-#[derive(Debug, PartialEq, Hash, Eq, Clone, Copy, PartialOrd, Ord)]
-pub struct Point(i64, i64);
+advent_of_code::solution!(14, generator = generate);
 
+type Point = Vec2<i64>;
 
-impl Point {
-    pub fn add(self, other:&Point) -> Point {
-        Point(self.0 + other.0, self.1 + other.1)
-    }
-    pub fn to_idx(self, width:usize) -> usize {
-        self.1 as usize * width + self.0 as usize
-    }
-}
-
-impl Sub for Point {
-    type Output = Point;
-
-    fn sub(self, other:Point) -> Point {
-        Point(self.0 - other.0, self.1 - other.1)
-    }
-}
-
-impl Add for Point {
-    type Output = Point;
-
-    fn add(self, other:Point) -> Point {
-        Point(self.0 + other.0, self.1 + other.1)
-    }
-}
-impl Add<i64> for Point {
-    type Output = Point;
-
-    fn add(self, other:i64) -> Point {
-        Point(self.0 + other, self.1 + other)
-    }
-}
-
-impl Mul for Point {
-    type Output = Point;
-
-    fn mul(self, other:Point) -> Point {
-        Point(self.0 * other.0, self.1 * other.1)
-    }
+/// Width and height of the room of the real input.
+const ROOM: (usize, usize) = (101, 103);
 
-}
-
-impl Mul<usize> for Point {
-    type Output = Point;
-
-    fn mul(self, other:usize) -> Point {
-        Point(self.0 * other as i64, self.1 * other as i64)
-    }
-}
-
-impl Mul<i64> for Point {
-    type Output = Point;
-
-    fn mul(self, other:i64) -> Point {
-        Point(self.0 * other, self.1 * other)
-    }
+/// The examples use a smaller room, declared as `param room: <width>,<height>` in their answers.
+fn room() -> (usize, usize) {
+    param("room").map_or(ROOM, |room| {
+        let (width, height) = room.split_once(',').expect("room is declared as `<width>,<height>`");
+        (width.parse().unwrap(), height.parse().unwrap())
+    })
 }
 
 #[derive(Debug, PartialEq)]
@@ -73,27 +35,24 @@ pub struct Robot {
 
 impl Robot {
     fn travel(self:&Robot, time:usize) -> Point {
-        self.pos + self.velo * time
+        self.pos + self.velo * time as i64
     }
-}
 
-impl FromStr for Robot {
-    type Err = std::num::ParseIntError;
-
-    fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let (pos, velo) = line.split_once(" ").unwrap();
-        let pos = pos.strip_prefix("p=").unwrap().split_once(',').unwrap();
-        let velo = velo.strip_prefix("v=").unwrap().split_once(',').unwrap();
-
-        Ok(Robot {
-            pos: Point(pos.0.parse().unwrap(), pos.1.parse().unwrap()),
-            velo: Point(
-                velo.0.parse::<i64>().unwrap().rem_euclid(101),
-                velo.1.parse::<i64>().unwrap().rem_euclid(103)
-                ),
-        })
+    fn parse_point(input: &str) -> IResult<'_, Point> {
+        map(separated_pair(int, char(','), int), |(x, y)| Point::new(x, y))(input)
     }
 
+    // `p=0,4 v=3,-3`
+    fn parse(line: &str) -> IResult<'_, Robot> {
+        map(
+            separated_pair(
+                preceded(literal("p="), Robot::parse_point),
+                space1,
+                preceded(literal("v="), Robot::parse_point),
+            ),
+            |(pos, velo)| Robot { pos, velo },
+        )(line)
+    }
 }
 
 
@@ -105,17 +64,14 @@ pub struct Game {
 }
 
 impl Game {
-    fn wrap(&self, position: Point) -> Point {
-        let (mut x, mut y) = (position.0 % self.width as i64, position.1 % self.height as i64);
+    fn new(input: &str, (width, height): (usize, usize)) -> Game {
+        let robots = parse_input(input, lines(Robot::parse)).unwrap_or_else(|e| panic!("{e}"));
 
-        if x < 0 {
-            x = self.width as i64 + x;
-        }
-        if y < 0 {
-            y = self.height as i64 + y;
-        }
+        Game { width, height, robots }
+    }
 
-        Point(x, y)
+    fn wrap(&self, position: Point) -> Point {
+        position.rem_euclid(Point::new(self.width as i64, self.height as i64))
     }
 
     fn solve_linear(self:&Game, time: usize) -> usize {
@@ -126,19 +82,19 @@ impl Game {
             .map(|robot| robot.travel(time))//new coordinates of each robot on a global map
             .map(|position| self.wrap(position))//coordinates wrapped into same canvas
             .fold([0; 4], |mut quads, position| {
-                 if position.0 < h {
-                     if position.1 < v {
+                 if position.x < h {
+                     if position.y < v {
                          quads[0] += 1;
                      }
-                     if position.1 > v {
+                     if position.y > v {
                          quads[2] += 1;
                      }
                  }
-                 if position.0 > h {
-                     if position.1 < v {
+                 if position.x > h {
+                     if position.y < v {
                          quads[1] += 1;
                      }
-                     if position.1 > v {
+                     if position.y > v {
                          quads[3] += 1;
                      }
                  }
@@ -157,51 +113,39 @@ impl Game {
 }
 
 
+// robots anywhere in the real room, with velocities like the real input
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut robots = vec![];
+    let mut len = 0;
+    while len < size.max(1) {
+        let robot = format!(
+            "p={},{} v={},{}",
+            rng.range(0..=100),
+            rng.range(0..=102),
+            rng.range(-99..=99),
+            rng.range(-99..=99)
+        );
+        len += robot.len() + 1;
+        robots.push(robot);
+    }
+    robots.join("\n") + "\n"
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    let game = Game {
-        width: 101,
-        height: 103,
-        robots: input.lines().into_iter().map(|l| l.parse::<Robot>().unwrap()).collect_vec(),
-    };
+    let game = Game::new(input, room());
 
     Some(game.solve_linear(100) as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let game = Game {
-        width: 101,
-        height: 103,
-        robots: input.lines().into_iter().map(|l| l.parse::<Robot>().unwrap()).collect_vec(),
-    };
+    let game = Game::new(input, room());
 
     let robots_count = game.robots.len();
 
     //lets be super dumb, and check if all points are un unique positions
-    let mut time = 1;
-    while time < 10000 {
-        let advanced = game.advance_robots(time);
-        if advanced.len() == robots_count {
-            break;
-        }
-        time = time + 1;
-    }
+    //every robot is back at its start after lcm(width, height) seconds, so only one period needs checking
+    let period = math::lcm(game.width, game.height);
+    let time = (1..=period).find(|&time| game.advance_robots(time).len() == robots_count)?;
 
     Some(time as u32)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-}
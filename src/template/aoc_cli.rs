@@ -84,14 +84,16 @@ fn get_input_path(day: Day) -> String {
     format!("data/inputs/{day}.txt")
 }
 
-fn get_puzzle_path(day: Day) -> String {
+pub fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
+/// The `AOC_YEAR` of the environment, or the one the binary was built with. Cargo only sets
+/// it for `cargo run`, so an installed `advent_of_code` falls back to the build-time value.
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+        Err(_) => option_env!("AOC_YEAR").and_then(|x| x.parse().ok()),
     }
 }
 
@@ -0,0 +1,203 @@
+/// Module that renders timings as an SVG bar chart with a logarithmic time axis.
+use std::fmt::Write;
+
+use crate::template::timings::{format_duration, PartTiming, Timings};
+
+const WIDTH: f64 = 720.0;
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_RIGHT: f64 = 24.0;
+const MARGIN_TOP: f64 = 48.0;
+const MARGIN_BOTTOM: f64 = 16.0;
+const BAR_HEIGHT: f64 = 9.0;
+const DAY_HEIGHT: f64 = 26.0;
+
+const PART_COLORS: [&str; 2] = ["#4e79a7", "#f28e2b"];
+const TEXT_COLOR: &str = "#888888";
+const GRID_COLOR: &str = "#88888844";
+
+/// Returns the path of the chart for a README table, relative to the project root.
+pub fn chart_path(year: Option<u16>) -> String {
+    year.map_or_else(
+        || "./.assets/benchmarks.svg".into(),
+        |year| format!("./.assets/benchmarks-{year}.svg"),
+    )
+}
+
+/// Range of the time axis in powers of ten, e.g. `(2, 6)` spans `100ns..1ms`.
+fn decades(timings: &Timings) -> (i32, i32) {
+    let nanos: Vec<f64> = timings
+        .data
+        .iter()
+        .flat_map(|t| [&t.part_1, &t.part_2])
+        .filter_map(|part| part.as_ref().map(|p| p.nanos))
+        .filter(|nanos| *nanos > 0.0)
+        .collect();
+
+    // NOTE: decades of realistic timings are small integers.
+    #[allow(clippy::cast_possible_truncation)]
+    let (min, max) = (
+        nanos
+            .iter()
+            .copied()
+            .fold(f64::INFINITY, f64::min)
+            .log10()
+            .floor() as i32,
+        nanos.iter().copied().fold(0.0, f64::max).log10().ceil() as i32,
+    );
+
+    if nanos.is_empty() {
+        (0, 3)
+    } else {
+        (min.max(0), max.max(min.max(0) + 1))
+    }
+}
+
+/// Horizontal position of `nanos` on a log scale spanning `decades`.
+fn scale(nanos: f64, (min, max): (i32, i32)) -> f64 {
+    let plot_width = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let ratio = (nanos.max(1.0).log10() - f64::from(min)) / f64::from(max - min);
+    MARGIN_LEFT + ratio.clamp(0.0, 1.0) * plot_width
+}
+
+/// Renders a chart with one row per day and one bar per part.
+pub fn render(timings: &Timings) -> String {
+    let decades = decades(timings);
+
+    // NOTE: the number of days is at most 31.
+    #[allow(clippy::cast_precision_loss)]
+    let plot_height = DAY_HEIGHT * timings.data.len().max(1) as f64;
+    let height = MARGIN_TOP + plot_height + MARGIN_BOTTOM;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="sans-serif" font-size="12">"#
+    );
+
+    // legend
+    for (i, color) in PART_COLORS.iter().enumerate() {
+        let x = MARGIN_LEFT + 80.0 * i as f64;
+        let _ = writeln!(
+            svg,
+            r#"  <rect x="{x}" y="8" width="10" height="10" fill="{color}"/><text x="{}" y="17" fill="{TEXT_COLOR}">Part {}</text>"#,
+            x + 14.0,
+            i + 1
+        );
+    }
+
+    // grid lines with a label for every power of ten
+    for decade in decades.0..=decades.1 {
+        let x = scale(10f64.powi(decade), decades);
+        let _ = writeln!(
+            svg,
+            r#"  <line x1="{x:.1}" y1="{}" x2="{x:.1}" y2="{}" stroke="{GRID_COLOR}"/><text x="{x:.1}" y="{}" fill="{TEXT_COLOR}" text-anchor="middle">{}</text>"#,
+            MARGIN_TOP - 4.0,
+            MARGIN_TOP + plot_height,
+            MARGIN_TOP - 10.0,
+            format_duration(10f64.powi(decade)).replace(".0", "")
+        );
+    }
+
+    if timings.data.is_empty() {
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{MARGIN_LEFT}" y="{}" fill="{TEXT_COLOR}">No benchmarks yet.</text>"#,
+            MARGIN_TOP + 16.0
+        );
+    }
+
+    for (row, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = MARGIN_TOP + DAY_HEIGHT * row as f64 + (DAY_HEIGHT - 2.0 * BAR_HEIGHT) / 2.0;
+
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{}" y="{}" fill="{TEXT_COLOR}" text-anchor="end">Day {}</text>"#,
+            MARGIN_LEFT - 8.0,
+            y + BAR_HEIGHT + 4.0,
+            timing.day
+        );
+
+        for (i, part) in [&timing.part_1, &timing.part_2].into_iter().enumerate() {
+            let Some(PartTiming { nanos, .. }) = part else {
+                continue;
+            };
+
+            let width = scale(*nanos, decades) - MARGIN_LEFT;
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="{MARGIN_LEFT}" y="{:.1}" width="{width:.1}" height="{BAR_HEIGHT}" fill="{}"><title>Day {} part {}: {}</title></rect>"#,
+                y + BAR_HEIGHT * i as f64,
+                PART_COLORS[i],
+                timing.day,
+                i + 1,
+                format_duration(*nanos)
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{chart_path, decades, render, scale, MARGIN_LEFT, WIDTH};
+    use crate::day;
+    use crate::template::timings::{PartTiming, Timing, Timings};
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some(PartTiming::new(250.0)),
+                    part_2: Some(PartTiming::new(40_000.0)),
+                    total_nanos: 40_250.0,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some(PartTiming::new(3_000_000.0)),
+                    part_2: None,
+                    total_nanos: 3_000_000.0,
+                },
+            ],
+            fingerprint: None,
+        }
+    }
+
+    #[test]
+    fn spans_decades_of_timings() {
+        assert_eq!(decades(&get_mock_timings()), (2, 7));
+        assert_eq!(decades(&Timings::default()), (0, 3));
+    }
+
+    #[test]
+    fn scales_logarithmically() {
+        let decades = (2, 7);
+        assert_eq!(scale(100.0, decades), MARGIN_LEFT);
+        assert_eq!(scale(10_000_000.0, decades), WIDTH - 24.0);
+        let (a, b, c) = (
+            scale(1_000.0, decades),
+            scale(10_000.0, decades),
+            scale(100_000.0, decades),
+        );
+        assert!((b - a - (c - b)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn renders_bars_per_part() {
+        let svg = render(&get_mock_timings());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert!(svg.contains("<title>Day 01 part 2: 40.0µs</title>"));
+        assert!(svg.contains(">1µs</text>"));
+    }
+
+    #[test]
+    fn names_charts_per_year() {
+        assert_eq!(chart_path(None), "./.assets/benchmarks.svg");
+        assert_eq!(chart_path(Some(2024)), "./.assets/benchmarks-2024.svg");
+    }
+}
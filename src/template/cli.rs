@@ -0,0 +1,1012 @@
+/// Module that parses the arguments of the `advent_of_code` binary into a typed [`Command`].
+///
+/// Every subcommand is described by a [`Spec`], which is the single source for validating flags,
+/// rendering `--help` and generating shell completions.
+use std::{ffi::OsString, fmt::Display, str::FromStr};
+
+use crate::template::commands::{
+    scaffold::ScaffoldOptions,
+    scale::ScaleOptions,
+    time::{ExportFormat, TimeOptions},
+};
+use crate::template::{completions, Day, DaySet};
+
+/// A parsed invocation of the binary.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Download {
+        days: DaySet,
+    },
+    Read {
+        day: Day,
+    },
+    Scaffold {
+        day: Day,
+        download: bool,
+        options: ScaffoldOptions,
+    },
+    Solve {
+        days: DaySet,
+        release: bool,
+        dhat: bool,
+        submit: Option<u8>,
+    },
+    All {
+        days: DaySet,
+        release: bool,
+    },
+    Time {
+        days: Option<DaySet>,
+        history: bool,
+        export: Option<ExportFormat>,
+        output: Option<String>,
+        import: Option<String>,
+        options: TimeOptions,
+    },
+    Scale {
+        day: Day,
+        options: ScaleOptions,
+    },
+    Stars,
+    #[cfg(feature = "today")]
+    Today {
+        wait: bool,
+    },
+    #[cfg(feature = "today")]
+    Next,
+    Completions {
+        shell: Shell,
+    },
+    /// Print the help of a subcommand, or the overview if not set.
+    Help {
+        command: Option<&'static str>,
+    },
+}
+
+/// A flag accepted by a subcommand.
+#[derive(Debug)]
+pub struct Flag {
+    pub name: &'static str,
+    /// Placeholder of the value, for flags that take one.
+    pub value: Option<&'static str>,
+    /// Possible values, offered by shell completions.
+    pub choices: &'static [&'static str],
+    pub help: &'static str,
+}
+
+/// Describes a subcommand.
+#[derive(Debug)]
+pub struct Spec {
+    pub name: &'static str,
+    pub about: &'static str,
+    /// Positional argument, e.g. `<day>` or `[<days>]` if optional.
+    pub argument: Option<(&'static str, &'static str)>,
+    pub flags: &'static [Flag],
+}
+
+const fn switch(name: &'static str, help: &'static str) -> Flag {
+    Flag {
+        name,
+        value: None,
+        choices: &[],
+        help,
+    }
+}
+
+const fn option(name: &'static str, value: &'static str, help: &'static str) -> Flag {
+    Flag {
+        name,
+        value: Some(value),
+        choices: &[],
+        help,
+    }
+}
+
+const DAY: (&str, &str) = ("<day>", "Day of the puzzle, e.g. `1`.");
+const DAYS: (&str, &str) = (
+    "<days>",
+    "Selection of days, e.g. `1-3,5` or `unsolved`. See \"Selecting days\" in the readme.",
+);
+const OPTIONAL_DAYS: (&str, &str) = (
+    "[<days>]",
+    "Selection of days, e.g. `1-3,5` or `unsolved`. All days if not set.",
+);
+
+const SPECS: &[Spec] = &[
+    Spec {
+        name: "scaffold",
+        about: "Create the solution, input and example files of a day.",
+        argument: Some(DAY),
+        flags: &[
+            switch(
+                "--download",
+                "Also download the input and puzzle description.",
+            ),
+            switch("--overwrite", "Replace an existing solution file."),
+            switch(
+                "--overwrite-data",
+                "Reset input, example and answer files that contain data.",
+            ),
+            switch(
+                "--dry-run",
+                "Print the planned file changes without executing them.",
+            ),
+            option(
+                "--template",
+                "<name>",
+                "Template of the solution file, e.g. `grid`.",
+            ),
+            option(
+                "--answer-type",
+                "<type>",
+                "Return type of the solution parts, defaults to `u32`.",
+            ),
+        ],
+    },
+    Spec {
+        name: "download",
+        about: "Download the inputs and puzzle descriptions of days with aoc-cli.",
+        argument: Some(DAYS),
+        flags: &[],
+    },
+    Spec {
+        name: "read",
+        about: "Print the puzzle description of a day with aoc-cli.",
+        argument: Some(DAY),
+        flags: &[],
+    },
+    Spec {
+        name: "solve",
+        about: "Run solutions against the real puzzle inputs.",
+        argument: Some(DAYS),
+        flags: &[
+            switch("--release", "Run an optimized build."),
+            option(
+                "--submit",
+                "<part>",
+                "Submit the answer of a part with aoc-cli.",
+            ),
+            switch("--dhat", "Profile heap allocations with DHAT."),
+        ],
+    },
+    Spec {
+        name: "all",
+        about: "Run several solutions in one go.",
+        argument: Some(OPTIONAL_DAYS),
+        flags: &[switch("--release", "Run an optimized build.")],
+    },
+    Spec {
+        name: "time",
+        about: "Benchmark solutions and optionally store the timings in the readme.",
+        argument: Some(OPTIONAL_DAYS),
+        flags: &[
+            switch(
+                "--all",
+                "Bench all days, including the ones that are already stored.",
+            ),
+            switch("--store", "Store the timings and update the readme."),
+            switch(
+                "--chart",
+                "Also render the timings as an SVG chart, with `--store`.",
+            ),
+            switch(
+                "--force",
+                "Store or import timings measured in a different environment.",
+            ),
+            switch(
+                "--history",
+                "Print the stored benchmark history instead of benching.",
+            ),
+            Flag {
+                name: "--export",
+                value: Some("<format>"),
+                choices: &["csv", "md", "json"],
+                help: "Export the stored timings, or the history with `--history`.",
+            },
+            option(
+                "--output",
+                "<path>",
+                "Write an export to a file instead of stdout.",
+            ),
+            option(
+                "--import",
+                "<path>",
+                "Merge timings from a CSV export into the stored ones.",
+            ),
+        ],
+    },
+    Spec {
+        name: "scale",
+        about: "Bench a day against generated inputs of doubling size.",
+        argument: Some(DAY),
+        flags: &[
+            option(
+                "--start",
+                "<bytes>",
+                "Size of the smallest input, defaults to 1000.",
+            ),
+            option("--steps", "<n>", "Number of input sizes, defaults to 5."),
+            option("--seed", "<n>", "Seed of the input generator."),
+        ],
+    },
+    Spec {
+        name: "stars",
+        about: "Update the stars table in the readme from the downloaded puzzles.",
+        argument: None,
+        flags: &[],
+    },
+    Spec {
+        name: "today",
+        about: "Scaffold, download and read the puzzle of today.",
+        argument: None,
+        flags: &[switch("--wait", "Wait until the puzzle unlocks.")],
+    },
+    Spec {
+        name: "next",
+        about: "Print when the next puzzle unlocks.",
+        argument: None,
+        flags: &[],
+    },
+    Spec {
+        name: "completions",
+        about: "Print a shell completion script.",
+        argument: Some(("<shell>", "One of `bash`, `zsh` or `fish`.")),
+        flags: &[],
+    },
+    Spec {
+        name: "help",
+        about: "Print the help of a command.",
+        argument: Some(("[<command>]", "Command to print the help of.")),
+        flags: &[],
+    },
+];
+
+/// Specs of all subcommands that are available in this build.
+pub fn specs() -> impl Iterator<Item = &'static Spec> {
+    SPECS
+        .iter()
+        .filter(|spec| cfg!(feature = "today") || !matches!(spec.name, "today" | "next"))
+}
+
+pub fn find_spec(name: &str) -> Option<&'static Spec> {
+    specs().find(|spec| spec.name == name)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug)]
+pub enum Error {
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownFlag(&'static str, String),
+    UnexpectedArguments(&'static str, Vec<String>),
+    Parse(&'static str, pico_args::Error),
+    /// A flag that only has an effect together with one of the `requires` flags.
+    MissingFlag {
+        command: &'static str,
+        flag: &'static str,
+        requires: &'static [&'static str],
+    },
+    /// A flag that has no effect together with another flag or argument.
+    ConflictingFlags {
+        command: &'static str,
+        flag: &'static str,
+        other: &'static str,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingCommand => write!(f, "no command specified, see `--help`."),
+            Error::UnknownCommand(name) => {
+                write!(f, "unknown command `{name}`, see `--help`.")
+            }
+            Error::UnknownFlag(command, flag) => {
+                write!(
+                    f,
+                    "unknown flag `{flag}` for `{command}`, see `{command} --help`."
+                )
+            }
+            Error::UnexpectedArguments(command, args) => write!(
+                f,
+                "unexpected argument(s) {} for `{command}`, see `{command} --help`.",
+                args.iter()
+                    .map(|arg| format!("`{arg}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Error::Parse(command, e) => write!(f, "{e}, see `{command} --help`."),
+            Error::MissingFlag {
+                command,
+                flag,
+                requires,
+            } => write!(
+                f,
+                "`{flag}` requires {}, see `{command} --help`.",
+                requires
+                    .iter()
+                    .map(|flag| format!("`{flag}`"))
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            ),
+            Error::ConflictingFlags {
+                command,
+                flag,
+                other,
+            } => write!(
+                f,
+                "`{flag}` cannot be combined with `{other}`, see `{command} --help`."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Shells that completion scripts can be generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(format!("unknown shell `{s}`, expecting bash, zsh or fish")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses the arguments of the binary, without the program name.
+pub fn parse(args: Vec<OsString>) -> Result<Command, Error> {
+    let raw: Vec<String> = args
+        .iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+
+    let Some(name) = raw.first() else {
+        return Err(Error::MissingCommand);
+    };
+
+    if matches!(name.as_str(), "-h" | "--help" | "help") {
+        return match raw.get(1) {
+            Some(command) => find_spec(command)
+                .map(|spec| Command::Help {
+                    command: Some(spec.name),
+                })
+                .ok_or(Error::UnknownCommand(command.clone())),
+            None => Ok(Command::Help { command: None }),
+        };
+    }
+
+    let spec = find_spec(name).ok_or(Error::UnknownCommand(name.clone()))?;
+    let rest = &raw[1..];
+
+    if rest.iter().any(|arg| arg == "-h" || arg == "--help") {
+        return Ok(Command::Help {
+            command: Some(spec.name),
+        });
+    }
+
+    validate_flags(spec, rest)?;
+
+    let mut args = pico_args::Arguments::from_vec(args[1..].to_vec());
+    let command = parse_command(spec.name, &mut args).map_err(|e| Error::Parse(spec.name, e))?;
+    check_flag_combinations(&command)?;
+
+    let remaining: Vec<String> = args
+        .finish()
+        .iter()
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+    if !remaining.is_empty() {
+        return Err(Error::UnexpectedArguments(spec.name, remaining));
+    }
+
+    Ok(command)
+}
+
+/// Rejects flags that `spec` does not accept, before they are mistaken for positional arguments.
+/// Options take their value from the next argument or after a `=`, e.g. `--export=csv`.
+fn validate_flags(spec: &Spec, args: &[String]) -> Result<(), Error> {
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') || arg == "-" {
+            continue;
+        }
+
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, _)) => (name, true),
+            None => (arg.as_str(), false),
+        };
+
+        match spec.flags.iter().find(|flag| flag.name == name) {
+            // skip the value of options, which may start with a dash.
+            Some(flag) if flag.value.is_some() => {
+                if !inline_value {
+                    args.next();
+                }
+            }
+            Some(_) if !inline_value => {}
+            _ => return Err(Error::UnknownFlag(spec.name, arg.clone())),
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects flags that would be silently ignored, either without another flag or because
+/// another flag selects a mode that does not use them.
+fn check_flag_combinations(command: &Command) -> Result<(), Error> {
+    let Command::Time {
+        days,
+        history,
+        export,
+        output,
+        import,
+        options,
+    } = command
+    else {
+        return Ok(());
+    };
+
+    let missing = |flag, requires| {
+        Err(Error::MissingFlag {
+            command: "time",
+            flag,
+            requires,
+        })
+    };
+    let conflict = |flag, other| {
+        Err(Error::ConflictingFlags {
+            command: "time",
+            flag,
+            other,
+        })
+    };
+
+    if output.is_some() && export.is_none() {
+        return missing("--output", &["--export"]);
+    }
+
+    // `--export` takes precedence over `--import`, which takes precedence over `--history`.
+    let mode = if export.is_some() {
+        Some("--export")
+    } else if import.is_some() {
+        Some("--import")
+    } else if *history {
+        Some("--history")
+    } else {
+        None
+    };
+
+    if let Some(mode) = mode {
+        let ignored = [
+            (import.is_some() && mode != "--import", "--import"),
+            (*history && mode == "--import", "--history"),
+            (days.is_some() && mode == "--import", "<days>"),
+            (options.all, "--all"),
+            (options.store, "--store"),
+            (options.chart, "--chart"),
+            (options.force && mode != "--import", "--force"),
+        ];
+        if let Some((_, flag)) = ignored.iter().find(|(set, _)| *set) {
+            return conflict(flag, mode);
+        }
+        return Ok(());
+    }
+
+    if options.chart && !options.store {
+        return missing("--chart", &["--store"]);
+    }
+    if options.force && !options.store {
+        return missing("--force", &["--store", "--import"]);
+    }
+    if options.all && days.is_some() {
+        return conflict("--all", "<days>");
+    }
+
+    Ok(())
+}
+
+fn parse_command(name: &str, args: &mut pico_args::Arguments) -> Result<Command, pico_args::Error> {
+    // NOTE: pico_args requires options to be consumed before free arguments.
+    Ok(match name {
+        "all" => {
+            let release = args.contains("--release");
+            Command::All {
+                days: args.opt_free_from_str()?.unwrap_or_else(DaySet::all),
+                release,
+            }
+        }
+        "time" => {
+            let history = args.contains("--history");
+            let export = args.opt_value_from_str("--export")?;
+            let output = args.opt_value_from_str("--output")?;
+            let import = args.opt_value_from_str("--import")?;
+            let options = TimeOptions {
+                all: args.contains("--all"),
+                store: args.contains("--store"),
+                chart: args.contains("--chart"),
+                force: args.contains("--force"),
+            };
+
+            Command::Time {
+                days: args.opt_free_from_str()?,
+                history,
+                export,
+                output,
+                import,
+                options,
+            }
+        }
+        "download" => Command::Download {
+            days: args.free_from_str()?,
+        },
+        "read" => Command::Read {
+            day: args.free_from_str()?,
+        },
+        "scaffold" => {
+            let download = args.contains("--download");
+            let options = ScaffoldOptions {
+                overwrite: args.contains("--overwrite"),
+                overwrite_data: args.contains("--overwrite-data"),
+                dry_run: args.contains("--dry-run"),
+                template: args.opt_value_from_str("--template")?,
+                answer_type: args.opt_value_from_str("--answer-type")?,
+            };
+
+            Command::Scaffold {
+                day: args.free_from_str()?,
+                download,
+                options,
+            }
+        }
+        "solve" => {
+            let release = args.contains("--release");
+            let submit = args.opt_value_from_str("--submit")?;
+            let dhat = args.contains("--dhat");
+
+            Command::Solve {
+                days: args.free_from_str()?,
+                release,
+                dhat,
+                submit,
+            }
+        }
+        "scale" => {
+            let defaults = ScaleOptions::default();
+            let options = ScaleOptions {
+                start: args
+                    .opt_value_from_str("--start")?
+                    .unwrap_or(defaults.start),
+                steps: args
+                    .opt_value_from_str("--steps")?
+                    .unwrap_or(defaults.steps),
+                seed: args.opt_value_from_str("--seed")?.unwrap_or(defaults.seed),
+            };
+
+            Command::Scale {
+                day: args.free_from_str()?,
+                options,
+            }
+        }
+        "stars" => Command::Stars,
+        #[cfg(feature = "today")]
+        "today" => Command::Today {
+            wait: args.contains("--wait"),
+        },
+        #[cfg(feature = "today")]
+        "next" => Command::Next,
+        "completions" => Command::Completions {
+            shell: args.free_from_str()?,
+        },
+        _ => unreachable!("every spec has a parser"),
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the help of `command`, or an overview of all commands if not set.
+pub fn help(command: Option<&str>) -> String {
+    match command.and_then(find_spec) {
+        Some(spec) => help_for(spec),
+        None => overview(),
+    }
+}
+
+fn overview() -> String {
+    let width = specs().map(|spec| spec.name.len()).max().unwrap_or(0);
+    let mut lines = vec![
+        "Usage: cargo <command> [<args>]".to_string(),
+        String::new(),
+        "Commands:".into(),
+    ];
+    lines.extend(specs().map(|spec| format!("  {:width$}  {}", spec.name, spec.about)));
+    lines.push(String::new());
+    lines.push("See `cargo <command> --help` for the arguments of a command.".into());
+    lines.join("\n")
+}
+
+fn help_for(spec: &Spec) -> String {
+    let mut usage = format!("Usage: cargo {}", spec.name);
+    if let Some((argument, _)) = spec.argument {
+        usage.push(' ');
+        usage.push_str(argument);
+    }
+    if !spec.flags.is_empty() {
+        usage.push_str(" [options]");
+    }
+
+    let mut lines = vec![usage, String::new(), spec.about.to_string()];
+
+    if let Some((argument, help)) = spec.argument {
+        lines.push(String::new());
+        lines.push("Arguments:".into());
+        lines.push(format!("  {argument}  {help}"));
+    }
+
+    let flags: Vec<(String, &str)> = spec
+        .flags
+        .iter()
+        .map(|flag| {
+            let name = match flag.value {
+                Some(value) => format!("{} {value}", flag.name),
+                None => flag.name.to_string(),
+            };
+            let help = flag.help;
+            (name, help)
+        })
+        .chain([("-h, --help".to_string(), "Print this help.")])
+        .collect();
+    let width = flags.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+
+    lines.push(String::new());
+    lines.push("Options:".into());
+    lines.extend(
+        flags
+            .iter()
+            .map(|(name, help)| format!("  {name:width$}  {help}")),
+    );
+
+    lines.join("\n")
+}
+
+/// Renders the completion script of `shell` for the binary `program`.
+pub fn completions(shell: Shell, program: &str) -> String {
+    completions::render(shell, program, &specs().collect::<Vec<_>>())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{help, parse, specs, Command, Error, Shell};
+    use crate::day;
+    use crate::template::commands::{
+        scaffold::ScaffoldOptions,
+        scale::ScaleOptions,
+        time::{ExportFormat, TimeOptions},
+    };
+    use crate::template::DaySet;
+
+    fn run(args: &str) -> Result<Command, Error> {
+        parse(args.split_whitespace().map(Into::into).collect())
+    }
+
+    fn days(s: &str) -> DaySet {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_scaffold() {
+        assert_eq!(
+            run("scaffold 5 --template grid --answer-type u64 --dry-run").unwrap(),
+            Command::Scaffold {
+                day: day!(5),
+                download: false,
+                options: ScaffoldOptions {
+                    dry_run: true,
+                    template: Some("grid".into()),
+                    answer_type: Some("u64".into()),
+                    ..ScaffoldOptions::default()
+                },
+            }
+        );
+        assert!(matches!(
+            run("scaffold 5 --download --overwrite --overwrite-data").unwrap(),
+            Command::Scaffold {
+                download: true,
+                options: ScaffoldOptions {
+                    overwrite: true,
+                    overwrite_data: true,
+                    ..
+                },
+                ..
+            }
+        ));
+        assert!(matches!(run("scaffold"), Err(Error::Parse("scaffold", _))));
+    }
+
+    #[test]
+    fn parses_download_and_read() {
+        assert_eq!(
+            run("download 1-3").unwrap(),
+            Command::Download { days: days("1-3") }
+        );
+        assert_eq!(run("read 7").unwrap(), Command::Read { day: day!(7) });
+        assert!(matches!(
+            run("read 7 8"),
+            Err(Error::UnexpectedArguments("read", _))
+        ));
+    }
+
+    #[test]
+    fn parses_solve() {
+        assert_eq!(
+            run("solve 3 --release --submit 2").unwrap(),
+            Command::Solve {
+                days: days("3"),
+                release: true,
+                dhat: false,
+                submit: Some(2),
+            }
+        );
+        assert!(matches!(
+            run("solve 3 --dhat").unwrap(),
+            Command::Solve { dhat: true, .. }
+        ));
+        assert!(matches!(
+            run("solve 3 --submit x"),
+            Err(Error::Parse("solve", _))
+        ));
+    }
+
+    #[test]
+    fn parses_all() {
+        assert_eq!(
+            run("all").unwrap(),
+            Command::All {
+                days: DaySet::all(),
+                release: false,
+            }
+        );
+        assert_eq!(
+            run("all odd --release").unwrap(),
+            Command::All {
+                days: days("odd"),
+                release: true,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_time() {
+        assert_eq!(
+            run("time --store --chart 1..5").unwrap(),
+            Command::Time {
+                days: Some(days("1..5")),
+                history: false,
+                export: None,
+                output: None,
+                import: None,
+                options: TimeOptions {
+                    store: true,
+                    chart: true,
+                    ..TimeOptions::default()
+                },
+            }
+        );
+        assert!(matches!(
+            run("time --history --export csv --output out.csv").unwrap(),
+            Command::Time {
+                days: None,
+                history: true,
+                export: Some(ExportFormat::Csv),
+                output: Some(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            run("time --import in.csv").unwrap(),
+            Command::Time {
+                import: Some(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            run("time --all --store --force").unwrap(),
+            Command::Time {
+                options: TimeOptions {
+                    all: true,
+                    store: true,
+                    force: true,
+                    ..
+                },
+                ..
+            }
+        ));
+        assert!(matches!(
+            run("time --export xml"),
+            Err(Error::Parse("time", _))
+        ));
+    }
+
+    #[test]
+    fn rejects_ignored_time_flags() {
+        let missing = |args: &str, expected: &str| {
+            assert!(
+                matches!(run(args), Err(Error::MissingFlag { flag, .. }) if flag == expected),
+                "{args}"
+            );
+        };
+        missing("time --chart 1", "--chart");
+        missing("time --output out.csv", "--output");
+        missing("time --history --output out.csv", "--output");
+        missing("time --force 1", "--force");
+
+        let conflict = |args: &str, expected: (&str, &str)| {
+            assert!(
+                matches!(
+                    run(args),
+                    Err(Error::ConflictingFlags { flag, other, .. }) if (flag, other) == expected
+                ),
+                "{args}"
+            );
+        };
+        conflict("time --export csv --store", ("--store", "--export"));
+        conflict("time --export csv --store --chart", ("--store", "--export"));
+        conflict("time --import in.csv --store", ("--store", "--import"));
+        conflict("time --import in.csv --chart", ("--chart", "--import"));
+        conflict("time --history --store", ("--store", "--history"));
+        conflict("time --history --chart", ("--chart", "--history"));
+        conflict("time --history --import in.csv", ("--history", "--import"));
+        conflict(
+            "time --export csv --import in.csv",
+            ("--import", "--export"),
+        );
+        conflict("time --import in.csv 1-3", ("<days>", "--import"));
+        conflict("time --history --all", ("--all", "--history"));
+        conflict("time --export md --force", ("--force", "--export"));
+        conflict("time --all 1", ("--all", "<days>"));
+
+        assert!(run("time --import in.csv --force").is_ok());
+        assert!(run("time --all --store --chart --force").is_ok());
+        assert!(run("time --history --export json --output out.json 1-3").is_ok());
+    }
+
+    #[test]
+    fn parses_inline_values() {
+        assert!(matches!(
+            run("scaffold 5 --template=grid --answer-type=u64").unwrap(),
+            Command::Scaffold {
+                options: ScaffoldOptions {
+                    template: Some(template),
+                    answer_type: Some(answer_type),
+                    ..
+                },
+                ..
+            } if template == "grid" && answer_type == "u64"
+        ));
+        assert!(matches!(
+            run("time --export=csv --output=out.csv").unwrap(),
+            Command::Time {
+                export: Some(ExportFormat::Csv),
+                output: Some(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            run("time --export=xml"),
+            Err(Error::Parse("time", _))
+        ));
+        assert!(matches!(
+            run("solve 1 --release=yes"),
+            Err(Error::UnknownFlag("solve", flag)) if flag == "--release=yes"
+        ));
+        assert!(matches!(
+            run("solve 1 --relase=1"),
+            Err(Error::UnknownFlag("solve", _))
+        ));
+    }
+
+    #[test]
+    fn parses_scale() {
+        assert_eq!(
+            run("scale 9 --start 500 --steps 3").unwrap(),
+            Command::Scale {
+                day: day!(9),
+                options: ScaleOptions {
+                    start: 500,
+                    steps: 3,
+                    ..ScaleOptions::default()
+                },
+            }
+        );
+        assert!(matches!(
+            run("scale 9 --seed 7").unwrap(),
+            Command::Scale {
+                options: ScaleOptions { seed: 7, .. },
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parses_stars_and_completions() {
+        assert_eq!(run("stars").unwrap(), Command::Stars);
+        assert_eq!(
+            run("completions zsh").unwrap(),
+            Command::Completions { shell: Shell::Zsh }
+        );
+        assert!(matches!(
+            run("completions powershell"),
+            Err(Error::Parse("completions", _))
+        ));
+    }
+
+    #[cfg(feature = "today")]
+    #[test]
+    fn parses_today_and_next() {
+        assert_eq!(run("today --wait").unwrap(), Command::Today { wait: true });
+        assert_eq!(run("next").unwrap(), Command::Next);
+    }
+
+    #[test]
+    fn parses_help() {
+        assert_eq!(run("--help").unwrap(), Command::Help { command: None });
+        assert_eq!(
+            run("help time").unwrap(),
+            Command::Help {
+                command: Some("time")
+            }
+        );
+        assert_eq!(
+            run("solve 1 -h").unwrap(),
+            Command::Help {
+                command: Some("solve")
+            }
+        );
+        assert!(matches!(run("help nope"), Err(Error::UnknownCommand(_))));
+    }
+
+    #[test]
+    fn rejects_unknown_input() {
+        assert!(matches!(run(""), Err(Error::MissingCommand)));
+        assert!(matches!(run("solv 1"), Err(Error::UnknownCommand(_))));
+        assert!(matches!(
+            run("solve --relase 1"),
+            Err(Error::UnknownFlag("solve", flag)) if flag == "--relase"
+        ));
+        assert!(matches!(
+            run("time --store 1 --al"),
+            Err(Error::UnknownFlag("time", _))
+        ));
+        assert!(matches!(
+            run("stars --all"),
+            Err(Error::UnknownFlag("stars", _))
+        ));
+    }
+
+    #[test]
+    fn renders_help() {
+        let overview = help(None);
+        assert!(specs().all(|spec| overview.contains(spec.name)));
+
+        let time = help(Some("time"));
+        assert!(time.starts_with("Usage: cargo time [<days>] [options]"));
+        assert!(time.contains("  --export <format>  Export the stored timings"));
+        assert!(time.contains("  -h, --help"));
+
+        assert!(help(Some("stars")).starts_with("Usage: cargo stars\n"));
+    }
+}
@@ -1,6 +1,12 @@
 pub mod all;
 pub mod download;
+#[cfg(feature = "today")]
+pub mod next;
 pub mod read;
+pub mod scale;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
@@ -0,0 +1,26 @@
+use chrono::{Local, Utc};
+
+use crate::template::unlock::{format_countdown, next_unlock};
+use crate::template::{aoc_cli, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle() {
+    let now = Utc::now();
+    let unlock = next_unlock(now);
+
+    println!(
+        "{ANSI_BOLD}Day {}{ANSI_RESET} of {} unlocks on {} (in {}).",
+        unlock.day,
+        unlock.year,
+        unlock
+            .time
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M %Z"),
+        format_countdown(now, unlock.time)
+    );
+
+    if let Some(year) = aoc_cli::get_year() {
+        if i32::from(year) != unlock.year {
+            println!("Note: `AOC_YEAR` is set to {year}.");
+        }
+    }
+}
@@ -1,79 +1,446 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
     process,
 };
 
+use crate::template::launch_config::{self, LAUNCH_CONFIG_PATH};
+use crate::template::solution_template::{self, TemplateContext, DEFAULT_TEMPLATE};
 use crate::template::Day;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Options accepted by the `scaffold` command.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScaffoldOptions {
+    /// Replace an existing module file.
+    pub overwrite: bool,
+    /// Reset non-empty input and example files.
+    pub overwrite_data: bool,
+    /// Print the planned file operations without executing them.
+    pub dry_run: bool,
+    pub template: Option<String>,
+    pub answer_type: Option<String>,
+}
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
-    }
-    file.truncate(true).write(true).open(path)
+#[derive(Debug)]
+pub enum Error {
+    Template(solution_template::Error),
+    Exists(String),
+    IO(String, io::Error),
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Template(e) => write!(f, "failed to load template: {e}"),
+            Error::Exists(path) => write!(
+                f,
+                "module file \"{path}\" already exists, pass `--overwrite` to replace it."
+            ),
+            Error::IO(path, e) => write!(f, "failed to write \"{path}\": {e}"),
+        }
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// A single file system change that is part of a scaffolding [`Plan`].
+#[derive(Debug, PartialEq)]
+pub enum Operation {
+    /// Create a file that does not exist yet.
+    Create {
+        label: &'static str,
+        path: PathBuf,
+        contents: String,
+    },
+    /// Replace an existing file, remembering its contents for rollback.
+    Overwrite {
+        label: &'static str,
+        path: PathBuf,
+        contents: String,
+        previous: Vec<u8>,
+    },
+    /// Leave an existing file untouched.
+    Keep {
+        label: &'static str,
+        path: PathBuf,
+        reason: &'static str,
+    },
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::Create { label, path, .. } => {
+                write!(f, "create {label} \"{}\"", path.display())
+            }
+            Operation::Overwrite { label, path, .. } => {
+                write!(f, "update {label} \"{}\"", path.display())
+            }
+            Operation::Keep {
+                label,
+                path,
+                reason,
+            } => write!(f, "keep {label} \"{}\" ({reason})", path.display()),
         }
-    };
+    }
+}
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+/// All file operations needed to scaffold a day, computed before anything is written.
+#[derive(Debug, Default)]
+pub struct Plan {
+    pub operations: Vec<Operation>,
+}
+
+impl Plan {
+    /// Builds the plan for scaffolding `day` relative to `root`.
+    pub fn new(root: &Path, day: Day, options: &ScaffoldOptions) -> Result<Self, Error> {
+        let template =
+            solution_template::load(options.template.as_deref().unwrap_or(DEFAULT_TEMPLATE))
+                .map_err(Error::Template)?;
+
+        let module = solution_template::render(
+            &template,
+            &TemplateContext::for_day(day, options.answer_type.as_deref()),
+        )
+        .map_err(Error::Template)?;
+
+        let mut plan = Plan::default();
+
+        let module_path = root.join(format!("src/bin/{day}.rs"));
+        plan.operations.push(match read_existing(&module_path)? {
+            None => Operation::Create {
+                label: "module file",
+                path: module_path,
+                contents: module,
+            },
+            Some(previous) if options.overwrite => Operation::Overwrite {
+                label: "module file",
+                path: module_path,
+                contents: module,
+                previous,
+            },
+            Some(_) => return Err(Error::Exists(module_path.display().to_string())),
+        });
+
+        for (label, folder) in [("input file", "inputs"), ("example file", "examples")] {
+            let path = root.join(format!("data/{folder}/{day}.txt"));
+            plan.operations
+                .push(data_operation(label, path, String::new(), options)?);
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+
+        let path = root.join(format!("data/examples/{day}.answers"));
+        plan.operations.push(data_operation(
+            "example answers",
+            path,
+            example_answers(day),
+            options,
+        )?);
+
+        // only manage debug configurations for users of the bundled VS Code setup.
+        if root.join(".vscode").is_dir() {
+            let path = root.join(LAUNCH_CONFIG_PATH);
+            plan.operations.push(launch_config_operation(path, day)?);
         }
+
+        Ok(plan)
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    /// Executes all operations in order. If one of them fails, the operations that already ran
+    /// are reverted so the day is either fully scaffolded or left untouched.
+    pub fn apply(&self) -> Result<(), Error> {
+        for (i, operation) in self.operations.iter().enumerate() {
+            if let Err(e) = execute(operation) {
+                for done in self.operations[..i].iter().rev() {
+                    if let Err(rollback_err) = revert(done) {
+                        eprintln!("Failed to roll back {done}: {rollback_err}");
+                    }
+                }
+                return Err(e);
+            }
         }
+        Ok(())
+    }
+}
+
+fn read_existing(path: &Path) -> Result<Option<Vec<u8>>, Error> {
+    match fs::read(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(Error::IO(path.display().to_string(), e)),
+    }
+}
+
+/// Placeholder answers for the example of `day`, see [`crate::template::examples`].
+fn example_answers(day: Day) -> String {
+    format!(
+        "# Answers to the example in `data/examples/{day}.txt`, checked by `cargo test --bin {day}`.\n# part_one: \n# part_two: \n"
+    )
+}
+
+fn data_operation(
+    label: &'static str,
+    path: PathBuf,
+    contents: String,
+    options: &ScaffoldOptions,
+) -> Result<Operation, Error> {
+    Ok(match read_existing(&path)? {
+        None => Operation::Create {
+            label,
+            path,
+            contents,
+        },
+        Some(previous) if previous.is_empty() => Operation::Keep {
+            label,
+            path,
+            reason: "already exists",
+        },
+        Some(previous) if options.overwrite_data => Operation::Overwrite {
+            label,
+            path,
+            contents,
+            previous,
+        },
+        Some(_) => Operation::Keep {
+            label,
+            path,
+            reason: "not empty, pass `--overwrite-data` to reset it",
+        },
+    })
+}
+
+fn launch_config_operation(path: PathBuf, day: Day) -> Result<Operation, Error> {
+    let label = "debug configuration";
+
+    let Some(previous) = read_existing(&path)? else {
+        return Ok(Operation::Create {
+            label,
+            path,
+            contents: launch_config::create(day),
+        });
+    };
+
+    Ok(
+        match launch_config::update(&String::from_utf8_lossy(&previous), day) {
+            Ok(Some(contents)) => Operation::Overwrite {
+                label,
+                path,
+                contents,
+                previous,
+            },
+            Ok(None) => Operation::Keep {
+                label,
+                path,
+                reason: "already configured for this day",
+            },
+            Err(_) => Operation::Keep {
+                label,
+                path,
+                reason: "could not locate `configurations`",
+            },
+        },
+    )
+}
+
+fn execute(operation: &Operation) -> Result<(), Error> {
+    let result = match operation {
+        Operation::Create { path, contents, .. } => OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(contents.as_bytes())),
+        Operation::Overwrite { path, contents, .. } => fs::write(path, contents),
+        Operation::Keep { .. } => Ok(()),
+    };
+
+    result.map_err(|e| match operation {
+        Operation::Create { path, .. }
+        | Operation::Overwrite { path, .. }
+        | Operation::Keep { path, .. } => Error::IO(path.display().to_string(), e),
+    })
+}
+
+fn revert(operation: &Operation) -> Result<(), io::Error> {
+    match operation {
+        Operation::Create { path, .. } => fs::remove_file(path),
+        Operation::Overwrite { path, previous, .. } => fs::write(path, previous),
+        Operation::Keep { .. } => Ok(()),
+    }
+}
+
+pub fn handle(day: Day, options: &ScaffoldOptions) {
+    let plan = match Plan::new(Path::new(""), day, options) {
+        Ok(plan) => plan,
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("Failed to scaffold day {day}: {e}");
             process::exit(1);
         }
-    }
+    };
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    if options.dry_run {
+        println!("Dry run, no files were changed. Scaffolding would:");
+        for operation in &plan.operations {
+            println!(" - {operation}");
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+        return;
+    }
+
+    if let Err(e) = plan.apply() {
+        eprintln!("Failed to scaffold day {day}, reverted all changes: {e}");
+        process::exit(1);
+    }
+
+    for operation in &plan.operations {
+        match operation {
+            Operation::Create {
+                label,
+                path,
+                contents,
+            } => {
+                let empty = if contents.is_empty() { "empty " } else { "" };
+                println!("Created {empty}{label} \"{}\"", path.display());
+            }
+            Operation::Overwrite { label, path, .. } => {
+                println!("Updated {label} \"{}\"", path.display());
+            }
+            Operation::Keep {
+                label,
+                path,
+                reason,
+            } => {
+                println!("Kept {label} \"{}\" ({reason})", path.display());
+            }
         }
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Operation, Plan, ScaffoldOptions};
+    use crate::day;
+    use std::{env, fs, path::PathBuf};
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for dir in ["src/bin", "data/inputs", "data/examples"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn creates_all_files() {
+        let root = temp_root("create");
+        let plan = Plan::new(&root, day!(10), &ScaffoldOptions::default()).unwrap();
+        plan.apply().unwrap();
+
+        let module = fs::read_to_string(root.join("src/bin/10.rs")).unwrap();
+        assert!(module.contains("advent_of_code::solution!(10);"));
+        assert!(root.join("data/inputs/10.txt").exists());
+        assert!(root.join("data/examples/10.txt").exists());
+        assert!(fs::read_to_string(root.join("data/examples/10.answers"))
+            .unwrap()
+            .contains("# part_one: "));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn refuses_existing_module_without_overwrite() {
+        let root = temp_root("exists");
+        fs::write(root.join("src/bin/10.rs"), "// my solution").unwrap();
+        assert!(Plan::new(&root, day!(10), &ScaffoldOptions::default()).is_err());
+
+        let options = ScaffoldOptions {
+            overwrite: true,
+            ..ScaffoldOptions::default()
+        };
+        let plan = Plan::new(&root, day!(10), &options).unwrap();
+        assert!(matches!(plan.operations[0], Operation::Overwrite { .. }));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn keeps_non_empty_inputs() {
+        let root = temp_root("keep");
+        fs::write(root.join("data/inputs/10.txt"), "1 2 3").unwrap();
+        let options = ScaffoldOptions {
+            overwrite: true,
+            ..ScaffoldOptions::default()
+        };
+        let plan = Plan::new(&root, day!(10), &options).unwrap();
+        assert!(matches!(plan.operations[1], Operation::Keep { .. }));
+        plan.apply().unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("data/inputs/10.txt")).unwrap(),
+            "1 2 3"
+        );
+
+        let options = ScaffoldOptions {
+            overwrite: true,
+            overwrite_data: true,
+            ..ScaffoldOptions::default()
+        };
+        let plan = Plan::new(&root, day!(10), &options).unwrap();
+        assert!(matches!(plan.operations[1], Operation::Overwrite { .. }));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn adds_debug_configurations() {
+        let root = temp_root("launch");
+        let plan = Plan::new(&root, day!(10), &ScaffoldOptions::default()).unwrap();
+        assert_eq!(plan.operations.len(), 4);
+
+        fs::create_dir_all(root.join(".vscode")).unwrap();
+        let plan = Plan::new(&root, day!(10), &ScaffoldOptions::default()).unwrap();
+        plan.apply().unwrap();
+        let launch = fs::read_to_string(root.join(".vscode/launch.json")).unwrap();
+        assert!(launch.contains("Debug unit tests for day 10"));
+
+        let options = ScaffoldOptions {
+            overwrite: true,
+            ..ScaffoldOptions::default()
+        };
+        let plan = Plan::new(&root, day!(10), &options).unwrap();
+        assert!(matches!(plan.operations[4], Operation::Keep { .. }));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn rolls_back_on_failure() {
+        let root = temp_root("rollback");
+        fs::write(root.join("data/inputs/10.txt"), "old").unwrap();
+
+        let plan = Plan {
+            operations: vec![
+                Operation::Create {
+                    label: "module file",
+                    path: root.join("src/bin/10.rs"),
+                    contents: "new".into(),
+                },
+                Operation::Overwrite {
+                    label: "input file",
+                    path: root.join("data/inputs/10.txt"),
+                    contents: String::new(),
+                    previous: b"old".to_vec(),
+                },
+                Operation::Create {
+                    label: "example file",
+                    path: root.join("missing/dir/10.txt"),
+                    contents: String::new(),
+                },
+            ],
+        };
+
+        assert!(plan.apply().is_err());
+        assert!(!root.join("src/bin/10.rs").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("data/inputs/10.txt")).unwrap(),
+            "old"
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
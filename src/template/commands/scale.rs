@@ -0,0 +1,107 @@
+use std::{env, fs, path::Path, process};
+
+use crate::template::run_multi::{child_commands, get_path_for_bin};
+use crate::template::scale::{describe_exponent, growth_exponent, sizes};
+use crate::template::timings::format_duration;
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::util::prop::DEFAULT_SEED;
+
+/// Options accepted by the `scale` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaleOptions {
+    /// Size of the smallest generated input in bytes.
+    pub start: usize,
+    /// Number of sizes to bench, each twice as large as the one before.
+    pub steps: usize,
+    pub seed: u64,
+}
+
+impl Default for ScaleOptions {
+    fn default() -> Self {
+        Self {
+            start: 1000,
+            steps: 5,
+            seed: DEFAULT_SEED,
+        }
+    }
+}
+
+pub fn handle(day: Day, options: &ScaleOptions) {
+    if !Path::new(&get_path_for_bin(day)).exists() {
+        eprintln!("Day {day} has not been scaffolded yet.");
+        process::exit(1);
+    }
+
+    if options.steps < 2 {
+        eprintln!("Scaling needs at least two sizes, pass `--steps 2` or more.");
+        process::exit(1);
+    }
+
+    let mut samples: [Vec<(f64, f64)>; 2] = [vec![], vec![]];
+
+    for size in sizes(options.start, options.steps) {
+        let input = match child_commands::generate_input(day, size, options.seed) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Failed to generate an input for day {day}: {e}");
+                process::exit(1);
+            }
+        };
+
+        let path = env::temp_dir().join(format!("aoc-scale-{day}-{size}.txt"));
+        if let Err(e) = fs::write(&path, &input) {
+            eprintln!("Failed to write \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+
+        println!(
+            "{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}({} bytes){ANSI_RESET}",
+            input.len()
+        );
+        println!("------");
+
+        let output = child_commands::run_solution_with_args(
+            day,
+            true,
+            &["--time", "--input", &path.to_string_lossy()],
+        );
+        let _ = fs::remove_file(&path);
+        let output = match output {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Failed to run day {day} on the generated input: {e:?}");
+                process::exit(1);
+            }
+        };
+
+        let timing = child_commands::parse_exec_time(&output, day);
+
+        // NOTE: generated inputs are far smaller than 2^52 bytes.
+        #[allow(clippy::cast_precision_loss)]
+        let bytes = input.len() as f64;
+        for (part, timing) in [&timing.part_1, &timing.part_2].into_iter().enumerate() {
+            if let Some(timing) = timing {
+                samples[part].push((bytes, timing.nanos));
+            }
+        }
+        println!();
+    }
+
+    for (i, samples) in samples.iter().enumerate() {
+        let description = match growth_exponent(samples) {
+            Some(exponent) => {
+                let class = describe_exponent(exponent)
+                    .map(|class| format!(", ~{class}"))
+                    .unwrap_or_default();
+                let range = format!(
+                    "{} → {}",
+                    format_duration(samples[0].1),
+                    format_duration(samples[samples.len() - 1].1)
+                );
+                format!("time ∝ n^{ANSI_BOLD}{exponent:.2}{ANSI_RESET} {ANSI_ITALIC}({range}{class}){ANSI_RESET}")
+            }
+            None => "-".into(),
+        };
+        println!("{ANSI_BOLD}Part {}{ANSI_RESET}: {description}", i + 1);
+    }
+}
@@ -0,0 +1,18 @@
+use std::process;
+
+use crate::template::{aoc_cli, readme_stars};
+
+pub fn handle() {
+    let Some(year) = aoc_cli::get_year() else {
+        eprintln!("AOC_YEAR is not set, cannot link to the puzzles.");
+        process::exit(1);
+    };
+
+    match readme_stars::update(year) {
+        Ok(total) => println!("Updated readme with {total} ⭐."),
+        Err(e) => {
+            eprintln!("Failed to update readme stars: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,40 +1,252 @@
-use std::collections::HashSet;
+use std::{fs, process};
 
-use crate::template::run_multi::run_multi;
+use crate::template::fingerprint::Fingerprint;
+use crate::template::run_multi::{child_commands, run_multi};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::timings_export::{export_history, export_timings, import_csv};
+use crate::template::timings_history::{self, HistoryEntry, PartSample};
+use crate::template::{all_days, readme_benchmarks, DaySet, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub use crate::template::timings_export::Format as ExportFormat;
+
+/// Number of most recent runs shown in the trend of `--history`.
+const TREND_LENGTH: usize = 8;
+
+/// Flags of the `time` command.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TimeOptions {
+    /// Bench all days, including the ones that are already stored.
+    pub all: bool,
+    pub store: bool,
+    pub chart: bool,
+    /// Store timings even if they were measured in a different environment than the stored ones.
+    pub force: bool,
+}
+
+pub fn handle(days: Option<DaySet>, options: &TimeOptions) {
     let stored_timings = Timings::read_from_file();
+    let fingerprint = Fingerprint::detect("release", &child_commands::features());
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+    // NOTE: check before benching, so that a mismatch does not waste a run.
+    if options.store && !options.force {
+        check_fingerprint(stored_timings.fingerprint.as_ref(), &fingerprint);
+    }
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let days_to_run = days.unwrap_or_else(|| {
+        if options.all {
+            DaySet::all()
+        } else {
+            // when the `--all` flag is not set, filter out days that are fully benched.
+            all_days()
+                .filter(|day| !stored_timings.is_day_complete(*day))
+                .collect()
+        }
+    });
 
-    if store {
+    let timings = Timings {
+        fingerprint: Some(fingerprint),
+        ..run_multi(&days_to_run, true, true).unwrap()
+    };
+
+    if options.store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        if let Err(e) = timings_history::append(&HistoryEntry::new(timings, "release")) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, options.chart) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
+            }
+        }
+    }
+}
+
+/// Exits if the stored timings were measured in a different environment than `current`.
+fn check_fingerprint(stored: Option<&Fingerprint>, current: &Fingerprint) {
+    let Some(stored) = stored else {
+        return;
+    };
+
+    let differences = stored.differences(current);
+    if differences.is_empty() {
+        return;
+    }
+
+    eprintln!("The stored timings were measured in a different environment:");
+    for (name, stored, current) in differences {
+        eprintln!("  {name}: {stored} (stored) → {current} (current)");
+    }
+    eprintln!("Re-run with --force to store the timings anyway, or with --all --force to replace all stored timings.");
+    process::exit(1);
+}
+
+/// Prints the stored benchmark history for `days`, or for every benchmarked day if not set.
+pub fn handle_history(days: Option<DaySet>) {
+    let history = match timings_history::read() {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {e}");
+            process::exit(1);
+        }
+    };
+
+    let days = days.unwrap_or_else(|| {
+        all_days()
+            .filter(|day| {
+                history
+                    .iter()
+                    .any(|entry| entry.timings.data.iter().any(|t| t.day == *day))
+            })
+            .collect()
+    });
+
+    if history.is_empty() || days.is_empty() {
+        println!("No benchmark history yet. Run `cargo time --store` to record one.");
+        return;
+    }
+
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        for part in 1..=2 {
+            let samples = timings_history::samples(&history, day, part);
+            println!("Part {part}: {}", describe_part(&samples));
+        }
+    }
+}
+
+fn describe_part(samples: &[PartSample]) -> String {
+    let (Some(latest), Some(best)) = (
+        samples.last(),
+        samples
+            .iter()
+            .min_by(|a, b| a.timing.nanos.total_cmp(&b.timing.nanos)),
+    ) else {
+        return "-".into();
+    };
+
+    let describe = |sample: &PartSample| {
+        format!(
+            "{ANSI_BOLD}{}{ANSI_RESET} {ANSI_ITALIC}({}, {}){ANSI_RESET}",
+            sample.timing,
+            sample.entry.revision(),
+            timings_history::format_timestamp(sample.entry.timestamp)
+        )
+    };
+
+    let runs = if samples.len() == 1 { "run" } else { "runs" };
+    let mut description = format!(
+        "latest {}, best {}, {} {runs}",
+        describe(latest),
+        describe(best),
+        samples.len()
+    );
+
+    if samples.len() > 1 {
+        let trend: Vec<String> = samples
+            .iter()
+            .skip(samples.len().saturating_sub(TREND_LENGTH))
+            .map(|s| s.timing.to_string())
+            .collect();
+        description.push_str(&format!("\n        {}", trend.join(" → ")));
+    }
+
+    description
+}
+
+/// Writes the stored timings, or every run of the history if `history` is set, to `output` or stdout.
+pub fn handle_export(
+    days: Option<DaySet>,
+    format: ExportFormat,
+    history: bool,
+    output: Option<&str>,
+) {
+    let keep = |timings: Timings| Timings {
+        data: timings
+            .data
+            .into_iter()
+            .filter(|t| days.as_ref().is_none_or(|days| days.contains(t.day)))
+            .collect(),
+        ..timings
+    };
+
+    let exported = if history {
+        match timings_history::read() {
+            Ok(history) => export_history(
+                &history
+                    .into_iter()
+                    .map(|entry| HistoryEntry {
+                        timings: keep(entry.timings),
+                        ..entry
+                    })
+                    .collect::<Vec<_>>(),
+                format,
+            ),
+            Err(e) => {
+                eprintln!("Failed to read benchmark history: {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        export_timings(&keep(Timings::read_from_file()), format)
+    };
+
+    match output {
+        Some(path) => {
+            if let Err(e) = fs::write(path, exported) {
+                eprintln!("Failed to write \"{path}\": {e}");
+                process::exit(1);
             }
+            println!("Exported benchmarks to \"{path}\".");
+        }
+        None => print!("{exported}"),
+    }
+}
+
+/// Merges timings from a CSV file in the format of `--export csv` into the stored timings.
+pub fn handle_import(path: &str, force: bool) {
+    let imported = match fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|csv| import_csv(&csv))
+    {
+        Ok(timings) => timings,
+        Err(e) => {
+            eprintln!("Failed to import \"{path}\": {e}");
+            process::exit(1);
+        }
+    };
+
+    let stored_timings = Timings::read_from_file();
+
+    // NOTE: CSV files do not record the environment, so they can't be checked against the stored fingerprint.
+    if let Some(stored) = stored_timings.fingerprint.as_ref().filter(|_| !force) {
+        eprintln!("The stored timings were measured in a known environment, but \"{path}\" does not record one:");
+        eprintln!("  stored: {stored}");
+        eprintln!("Re-run with --force to import the timings anyway.");
+        process::exit(1);
+    }
+
+    let merged_timings = stored_timings.merge(&imported);
+    merged_timings.store_file().unwrap();
+
+    match readme_benchmarks::update(merged_timings, false) {
+        Ok(()) => {
+            println!("Imported benchmarks of {} day(s).", imported.data.len());
+        }
+        Err(e) => {
+            eprintln!("Failed to store imported benchmarks: {e}");
         }
     }
 }
@@ -0,0 +1,109 @@
+use std::{
+    cmp,
+    io::{stdout, Write},
+    path::Path,
+    process, thread,
+    time::Duration,
+};
+
+use chrono::Utc;
+
+use crate::template::commands::{read, scaffold};
+use crate::template::unlock::{format_countdown, next_unlock};
+use crate::template::{aoc_cli, Day, ANSI_BOLD, ANSI_RESET};
+
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 8;
+
+/// Delay before the next download attempt, doubling from one second up to a minute.
+fn backoff_delay(attempt: u32) -> Duration {
+    Duration::from_secs(cmp::min(1 << attempt.min(6), 60))
+}
+
+fn is_scaffolded(day: Day) -> bool {
+    Path::new(&format!("src/bin/{day}.rs")).exists()
+}
+
+/// Shows a countdown until the next puzzle unlocks and returns its day.
+fn wait_for_next_unlock() -> Day {
+    let unlock = next_unlock(Utc::now());
+
+    if let Some(year) = aoc_cli::get_year() {
+        if i32::from(year) != unlock.year {
+            eprintln!(
+                "The next puzzle is day {} of {}, but `AOC_YEAR` is set to {year}.",
+                unlock.day, unlock.year
+            );
+            process::exit(1);
+        }
+    }
+
+    let mut stdout = stdout();
+
+    loop {
+        let now = Utc::now();
+        if now >= unlock.time {
+            break;
+        }
+
+        print!(
+            "\r⏳ {ANSI_BOLD}Day {}{ANSI_RESET} unlocks in {} ",
+            unlock.day,
+            format_countdown(now, unlock.time)
+        );
+        let _ = stdout.flush();
+
+        let remaining = (unlock.time - now).to_std().unwrap_or_default();
+        thread::sleep(cmp::min(remaining, Duration::from_secs(1)));
+    }
+
+    println!(
+        "\r🔓 {ANSI_BOLD}Day {}{ANSI_RESET} is unlocked!        ",
+        unlock.day
+    );
+    unlock.day
+}
+
+/// Downloads the input for `day`, retrying with exponential backoff while it is not available yet.
+fn download_with_retry(day: Day) {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    for attempt in 1..=MAX_DOWNLOAD_ATTEMPTS {
+        match aoc_cli::download(day) {
+            Ok(_) => return,
+            Err(e) if attempt < MAX_DOWNLOAD_ATTEMPTS => {
+                let delay = backoff_delay(attempt - 1);
+                eprintln!(
+                    "Download attempt {attempt}/{MAX_DOWNLOAD_ATTEMPTS} failed ({e}), retrying in {}s...",
+                    delay.as_secs()
+                );
+                thread::sleep(delay);
+            }
+            Err(e) => {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+pub fn handle(wait: bool) {
+    let day = match Day::today() {
+        Some(day) if !wait || !is_scaffolded(day) => day,
+        _ if wait => wait_for_next_unlock(),
+        _ => {
+            eprintln!(
+                "`today` command can only be run between december 1 and \
+                december {}. Please use `scaffold` with a specific day or pass `--wait`.",
+                crate::template::LAST_DAY
+            );
+            process::exit(1)
+        }
+    };
+
+    scaffold::handle(day, &scaffold::ScaffoldOptions::default());
+    download_with_retry(day);
+    read::handle(day);
+}
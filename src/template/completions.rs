@@ -0,0 +1,274 @@
+/// Module that renders shell completion scripts from the specs of the subcommands.
+use crate::template::cli::{Flag, Shell, Spec};
+
+pub fn render(shell: Shell, program: &str, specs: &[&Spec]) -> String {
+    match shell {
+        Shell::Bash => bash(program, specs),
+        Shell::Zsh => zsh(program, specs),
+        Shell::Fish => fish(program, specs),
+    }
+}
+
+/// Values of the positional argument of `spec` that can be completed.
+fn argument_choices(spec: &Spec, specs: &[&Spec]) -> Vec<&'static str> {
+    match spec.name {
+        "completions" => vec!["bash", "zsh", "fish"],
+        "help" => specs.iter().map(|spec| spec.name).collect(),
+        _ => vec![],
+    }
+}
+
+fn takes_path(flag: &Flag) -> bool {
+    flag.value == Some("<path>")
+}
+
+/// Name of the shell function, which may not contain dashes.
+fn function_name(program: &str) -> String {
+    format!("_{}", program.replace('-', "_"))
+}
+
+fn bash(program: &str, specs: &[&Spec]) -> String {
+    let function = function_name(program);
+    let names: Vec<&str> = specs.iter().map(|spec| spec.name).collect();
+
+    let mut lines = vec![
+        format!("{function}() {{"),
+        "    local cur prev".into(),
+        "    cur=\"${COMP_WORDS[COMP_CWORD]}\"".into(),
+        "    prev=\"${COMP_WORDS[COMP_CWORD-1]}\"".into(),
+        String::new(),
+        "    if [ \"$COMP_CWORD\" -eq 1 ]; then".into(),
+        format!(
+            "        COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+            names.join(" ")
+        ),
+        "        return".into(),
+        "    fi".into(),
+        String::new(),
+        "    case \"${COMP_WORDS[1]}\" in".into(),
+    ];
+
+    for spec in specs {
+        lines.push(format!("        {})", spec.name));
+
+        let values: Vec<&Flag> = spec.flags.iter().filter(|f| f.value.is_some()).collect();
+        if !values.is_empty() {
+            lines.push("            case \"$prev\" in".into());
+            for flag in values {
+                let reply = if !flag.choices.is_empty() {
+                    format!(
+                        "COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+                        flag.choices.join(" ")
+                    )
+                } else if takes_path(flag) {
+                    "COMPREPLY=($(compgen -f -- \"$cur\"))".into()
+                } else {
+                    "COMPREPLY=()".into()
+                };
+                lines.push(format!("                {}) {reply}; return ;;", flag.name));
+            }
+            lines.push("            esac".into());
+        }
+
+        let words: Vec<&str> = spec
+            .flags
+            .iter()
+            .map(|flag| flag.name)
+            .chain(argument_choices(spec, specs))
+            .chain(["--help"])
+            .collect();
+        lines.push(format!(
+            "            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+            words.join(" ")
+        ));
+        lines.push("            ;;".into());
+    }
+
+    lines.extend([
+        "    esac".into(),
+        "}".into(),
+        String::new(),
+        format!("complete -F {function} {program}"),
+        String::new(),
+    ]);
+    lines.join("\n")
+}
+
+/// Escapes `s` for a single-quoted zsh word that is interpreted by `_arguments` or `_describe`.
+fn zsh_escape(s: &str) -> String {
+    s.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+fn zsh(program: &str, specs: &[&Spec]) -> String {
+    let function = function_name(program);
+
+    let mut lines = vec![
+        format!("#compdef {program}"),
+        String::new(),
+        format!("{function}() {{"),
+        "    local -a commands".into(),
+        "    commands=(".into(),
+    ];
+    lines.extend(
+        specs
+            .iter()
+            .map(|spec| format!("        '{}:{}'", spec.name, zsh_escape(spec.about))),
+    );
+    lines.extend([
+        "    )".into(),
+        String::new(),
+        "    if (( CURRENT == 2 )); then".into(),
+        "        _describe 'command' commands".into(),
+        "        return".into(),
+        "    fi".into(),
+        String::new(),
+        "    shift words".into(),
+        "    (( CURRENT-- ))".into(),
+        String::new(),
+        "    case $words[1] in".into(),
+    ]);
+
+    for spec in specs {
+        lines.push(format!("        {})", spec.name));
+
+        let mut specs_of_args: Vec<String> = spec
+            .flags
+            .iter()
+            .map(|flag| {
+                let action = match flag.value {
+                    None => String::new(),
+                    Some(value) => {
+                        let value = value.trim_matches(['<', '>']);
+                        if !flag.choices.is_empty() {
+                            format!(":{value}:({})", flag.choices.join(" "))
+                        } else if takes_path(flag) {
+                            format!(":{value}:_files")
+                        } else {
+                            format!(":{value}:")
+                        }
+                    }
+                };
+                format!("'{}[{}]{action}'", flag.name, zsh_escape(flag.help))
+            })
+            .collect();
+        specs_of_args.push("'(-h --help)'{-h,--help}'[Print this help.]'".into());
+
+        if let Some((argument, _)) = spec.argument {
+            let optional = if argument.starts_with('[') { ":" } else { "" };
+            let name = argument.trim_matches(['[', ']', '<', '>']);
+            let choices = argument_choices(spec, specs);
+            let action = if choices.is_empty() {
+                String::new()
+            } else {
+                format!("({})", choices.join(" "))
+            };
+            specs_of_args.push(format!("'1:{optional}{name}:{action}'"));
+        }
+
+        lines.push("            _arguments \\".into());
+        let last = specs_of_args.len() - 1;
+        for (i, arg) in specs_of_args.iter().enumerate() {
+            let continuation = if i == last { "" } else { " \\" };
+            lines.push(format!("                {arg}{continuation}"));
+        }
+        lines.push("            ;;".into());
+    }
+
+    lines.extend([
+        "    esac".into(),
+        "}".into(),
+        String::new(),
+        format!("{function} \"$@\""),
+        String::new(),
+    ]);
+    lines.join("\n")
+}
+
+/// Quotes `s` as a single-quoted fish string.
+fn fish_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn fish(program: &str, specs: &[&Spec]) -> String {
+    let mut lines = vec![format!("complete -c {program} -f")];
+
+    lines.extend(specs.iter().map(|spec| {
+        format!(
+            "complete -c {program} -n __fish_use_subcommand -a {} -d {}",
+            spec.name,
+            fish_quote(spec.about)
+        )
+    }));
+
+    for spec in specs {
+        let condition = fish_quote(&format!("__fish_seen_subcommand_from {}", spec.name));
+        let prefix = format!("complete -c {program} -n {condition}");
+
+        let choices = argument_choices(spec, specs);
+        if !choices.is_empty() {
+            lines.push(format!("{prefix} -a {}", fish_quote(&choices.join(" "))));
+        }
+
+        for flag in spec.flags {
+            let long = flag.name.trim_start_matches("--");
+            let value = match flag.value {
+                None => String::new(),
+                Some(_) if !flag.choices.is_empty() => {
+                    format!(" -x -a {}", fish_quote(&flag.choices.join(" ")))
+                }
+                Some(_) if takes_path(flag) => " -r -F".into(),
+                Some(_) => " -x".into(),
+            };
+            lines.push(format!(
+                "{prefix} -l {long}{value} -d {}",
+                fish_quote(flag.help)
+            ));
+        }
+        lines.push(format!("{prefix} -s h -l help -d 'Print this help.'"));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::template::cli::{completions, Shell};
+
+    #[test]
+    fn renders_bash() {
+        let script = completions(Shell::Bash, "advent_of_code");
+        assert!(script.contains("compgen -W \"scaffold download read solve"));
+        assert!(script.contains("--export) COMPREPLY=($(compgen -W \"csv md json\""));
+        assert!(script.contains("--output) COMPREPLY=($(compgen -f"));
+        assert!(script.ends_with("complete -F _advent_of_code advent_of_code\n"));
+    }
+
+    #[test]
+    fn renders_zsh() {
+        let script = completions(Shell::Zsh, "advent_of_code");
+        assert!(script.starts_with("#compdef advent_of_code\n"));
+        assert!(script.contains("'time:Benchmark solutions"));
+        assert!(script.contains("'--export[Export the stored timings, or the history with `--history`.]:format:(csv md json)'"));
+        assert!(script.contains(
+            "'--import[Merge timings from a CSV export into the stored ones.]:path:_files'"
+        ));
+        assert!(script.contains("'1::days:'"));
+        assert!(script.contains("'1:shell:(bash zsh fish)'"));
+    }
+
+    #[test]
+    fn renders_fish() {
+        let script = completions(Shell::Fish, "advent_of_code");
+        assert!(script.contains("complete -c advent_of_code -n __fish_use_subcommand -a scale -d "));
+        assert!(script.contains(
+            "complete -c advent_of_code -n '__fish_seen_subcommand_from time' -l export -x -a 'csv md json'"
+        ));
+        assert!(script.contains(
+            "-n '__fish_seen_subcommand_from solve' -l release -d 'Run an optimized build.'"
+        ));
+    }
+}
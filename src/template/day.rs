@@ -3,12 +3,63 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{Datelike, Utc};
 
 #[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+use crate::template::unlock;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// The last day of the configured event.
+///
+/// Read at compile time from the `AOC_DAYS` variable in `.cargo/config.toml`. If it is not set,
+/// the length of the event published for `AOC_YEAR` is used.
+pub const LAST_DAY: u8 = event_length(option_env!("AOC_YEAR"), option_env!("AOC_DAYS"));
+
+/// Number of days of the Advent of Code event in `year`.
+/// Events up to 2024 had 25 puzzles, starting with 2025 they have 12.
+pub const fn default_event_length(year: Option<u16>) -> u8 {
+    match year {
+        Some(year) if year >= 2025 => 12,
+        _ => 25,
+    }
+}
+
+const fn parse_number(s: &str) -> Option<u16> {
+    let bytes = s.as_bytes();
+    if bytes.is_empty() {
+        return None;
+    }
+
+    let mut value: u16 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        value = match value.checked_mul(10) {
+            Some(v) => v + (bytes[i] - b'0') as u16,
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(value)
+}
+
+const fn event_length(year: Option<&str>, days: Option<&str>) -> u8 {
+    let year = match year {
+        Some(year) => parse_number(year),
+        None => None,
+    };
+
+    match days {
+        None => default_event_length(year),
+        Some(days) => match parse_number(days) {
+            Some(days) if days >= 1 && days <= 31 => days as u8,
+            _ => panic!("`AOC_DAYS` must be a number between 1 and 31"),
+        },
+    }
+}
+
+/// A valid day number of advent (i.e. an integer in range 1 to [`LAST_DAY`]).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -25,7 +76,7 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > LAST_DAY {
             return None;
         }
         Some(Self(day))
@@ -45,11 +96,10 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a day of the event in december, `None` otherwise.
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        let today = unlock::server_time(Utc::now());
+        if today.month() == 12 && today.day() <= u32::from(LAST_DAY) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -94,18 +144,18 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {LAST_DAY}")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to [`LAST_DAY`].
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to [`LAST_DAY`].
 pub struct AllDays {
     current: u8,
 }
@@ -121,10 +171,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > LAST_DAY {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above `LAST_DAY`.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,15 +185,16 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// The day number is checked against the configured [`LAST_DAY`] at compile time.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::LAST_DAY,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a value between 1 and the configured `LAST_DAY`"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -154,7 +205,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, event_length, Day, LAST_DAY};
 
     #[test]
     fn all_days_iterator() {
@@ -162,30 +213,38 @@ mod tests {
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
-        assert_eq!(iter.next(), Some(Day(3)));
-        assert_eq!(iter.next(), Some(Day(4)));
-        assert_eq!(iter.next(), Some(Day(5)));
-        assert_eq!(iter.next(), Some(Day(6)));
-        assert_eq!(iter.next(), Some(Day(7)));
-        assert_eq!(iter.next(), Some(Day(8)));
-        assert_eq!(iter.next(), Some(Day(9)));
-        assert_eq!(iter.next(), Some(Day(10)));
-        assert_eq!(iter.next(), Some(Day(11)));
-        assert_eq!(iter.next(), Some(Day(12)));
-        assert_eq!(iter.next(), Some(Day(13)));
-        assert_eq!(iter.next(), Some(Day(14)));
-        assert_eq!(iter.next(), Some(Day(15)));
-        assert_eq!(iter.next(), Some(Day(16)));
-        assert_eq!(iter.next(), Some(Day(17)));
-        assert_eq!(iter.next(), Some(Day(18)));
-        assert_eq!(iter.next(), Some(Day(19)));
-        assert_eq!(iter.next(), Some(Day(20)));
-        assert_eq!(iter.next(), Some(Day(21)));
-        assert_eq!(iter.next(), Some(Day(22)));
-        assert_eq!(iter.next(), Some(Day(23)));
-        assert_eq!(iter.next(), Some(Day(24)));
-        assert_eq!(iter.next(), Some(Day(25)));
-        assert_eq!(iter.next(), None);
+        assert_eq!(iter.last(), Some(Day(LAST_DAY)));
+        assert_eq!(all_days().count(), usize::from(LAST_DAY));
+    }
+
+    #[test]
+    fn event_length_by_year() {
+        assert_eq!(event_length(Some("2024"), None), 25);
+        assert_eq!(event_length(Some("2015"), None), 25);
+        assert_eq!(event_length(Some("2025"), None), 12);
+        assert_eq!(event_length(None, None), 25);
+        assert_eq!(event_length(Some("2024"), Some("10")), 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_invalid_event_length() {
+        event_length(Some("2024"), Some("0"));
+    }
+
+    #[test]
+    fn validates_against_last_day() {
+        assert_eq!(Day::new(LAST_DAY).map(Day::into_inner), Some(LAST_DAY));
+        assert_eq!(Day::new(LAST_DAY + 1), None);
+        assert!("0".parse::<Day>().is_err());
+        assert_eq!(
+            (LAST_DAY + 1)
+                .to_string()
+                .parse::<Day>()
+                .unwrap_err()
+                .to_string(),
+            format!("expecting a day number between 1 and {LAST_DAY}")
+        );
     }
 }
 
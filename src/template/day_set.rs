@@ -0,0 +1,249 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{all_days, puzzle, Day, LAST_DAY};
+
+/// A sorted set of days, used by commands that operate on more than one day.
+///
+/// # Syntax
+/// A comma separated list of:
+///  - single days: `7`
+///  - inclusive ranges: `12-14` or `12..=14`
+///  - exclusive ranges: `1..5` (days 1 to 4), open ranges like `20..` are supported as well
+///  - selectors: `all`, `odd`, `even`, `solved` and `unsolved` (based on the stars in `data/puzzles`)
+///
+/// ```
+/// # use advent_of_code::template::DaySet;
+/// let days: DaySet = "1..5,7,12-14".parse().unwrap();
+/// assert_eq!(days.to_string(), "1-4,7,12-14")
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DaySet(BTreeSet<Day>);
+
+impl DaySet {
+    /// Returns a set that contains every day of the event.
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.contains(&day)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates the days in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<I: IntoIterator<Item = Day>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Display for DaySet {
+    /// Formats the set in its most compact syntax, e.g. `1-4,7`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut ranges: Vec<(u8, u8)> = vec![];
+        for day in self.iter().map(Day::into_inner) {
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == day => *end = day,
+                _ => ranges.push((day, day)),
+            }
+        }
+
+        let items: Vec<String> = ranges
+            .iter()
+            .map(|&(start, end)| {
+                if start == end {
+                    start.to_string()
+                } else {
+                    format!("{start}-{end}")
+                }
+            })
+            .collect();
+
+        f.write_str(&items.join(","))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = BTreeSet::new();
+
+        for item in s.split(',').map(str::trim) {
+            match item {
+                "all" => days.extend(all_days()),
+                "odd" => days.extend(all_days().filter(|day| day.into_inner() % 2 == 1)),
+                "even" => days.extend(all_days().filter(|day| day.into_inner() % 2 == 0)),
+                "solved" => days.extend(all_days().filter(|day| puzzle::read_stars(*day) == 2)),
+                "unsolved" => days.extend(all_days().filter(|day| puzzle::read_stars(*day) < 2)),
+                _ => {
+                    let (start, end) = parse_range(item)?;
+                    // NOTE: both bounds have been validated as days.
+                    days.extend((start..=end).filter_map(Day::new));
+                }
+            }
+        }
+
+        Ok(Self(days))
+    }
+}
+
+fn parse_bound(s: &str, default: u8, item: &str) -> Result<u8, DaySetFromStrError> {
+    if s.is_empty() {
+        return Ok(default);
+    }
+
+    s.parse::<Day>()
+        .map(Day::into_inner)
+        .map_err(|e| DaySetFromStrError::new(item, &e.to_string()))
+}
+
+/// Parses a single day or a range of days into inclusive bounds.
+fn parse_range(item: &str) -> Result<(u8, u8), DaySetFromStrError> {
+    let (start, end) = if let Some((start, end)) = item.split_once("..=") {
+        (
+            parse_bound(start, 1, item)?,
+            parse_bound(end, LAST_DAY, item)?,
+        )
+    } else if let Some((start, end)) = item.split_once("..") {
+        let start = parse_bound(start, 1, item)?;
+        // NOTE: the exclusive end may point one past the last day, e.g. `1..26`.
+        let end = if end.is_empty() || end.parse() == Ok(LAST_DAY + 1) {
+            LAST_DAY
+        } else {
+            parse_bound(end, LAST_DAY, item)? - 1
+        };
+        (start, end)
+    } else if let Some((start, end)) = item.split_once('-') {
+        (
+            parse_bound(start, 1, item)?,
+            parse_bound(end, LAST_DAY, item)?,
+        )
+    } else {
+        let day = item
+            .parse::<Day>()
+            .map_err(|e| DaySetFromStrError::new(item, &e.to_string()))?;
+        (day.into_inner(), day.into_inner())
+    };
+
+    if start > end {
+        return Err(DaySetFromStrError::new(item, "range is empty"));
+    }
+
+    Ok((start, end))
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError {
+    item: String,
+    reason: String,
+}
+
+impl DaySetFromStrError {
+    fn new(item: &str, reason: &str) -> Self {
+        Self {
+            item: item.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid day selection `{}`: {}", self.item, self.reason)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySet;
+    use crate::day;
+    use crate::template::LAST_DAY;
+
+    fn days(s: &str) -> Vec<u8> {
+        s.parse::<DaySet>()
+            .unwrap()
+            .iter()
+            .map(|d| d.into_inner())
+            .collect()
+    }
+
+    #[test]
+    fn parses_days_and_ranges() {
+        assert_eq!(days("7"), vec![7]);
+        assert_eq!(days("1..5,7,10-12"), vec![1, 2, 3, 4, 7, 10, 11, 12]);
+        assert_eq!(days("3..=5"), vec![3, 4, 5]);
+        assert_eq!(days("9, 5-6 ,5"), vec![5, 6, 9]);
+        assert_eq!(days("..3"), vec![1, 2]);
+        // ranges up to the last day, which depends on the configured event
+        let last = LAST_DAY;
+        assert_eq!(days(&format!("{}..", last - 1)), vec![last - 1, last]);
+        assert_eq!(
+            days(&format!("{}..{}", last - 1, last + 1)),
+            vec![last - 1, last]
+        );
+        assert_eq!(days("-2"), vec![1, 2]);
+    }
+
+    #[test]
+    fn parses_selectors() {
+        assert_eq!(
+            days("odd,2"),
+            (1..=LAST_DAY)
+                .filter(|d| d % 2 == 1 || *d == 2)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(days("even").len(), usize::from(LAST_DAY / 2));
+        assert_eq!(
+            days("all"),
+            DaySet::all()
+                .iter()
+                .map(|d| d.into_inner())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_selections() {
+        let after_last = (LAST_DAY + 1).to_string();
+        for s in ["", "0", &after_last, "5-3", "1..1", "1,,2", "foo", "1-x"] {
+            assert!(s.parse::<DaySet>().is_err(), "expected `{s}` to be invalid");
+        }
+        assert_eq!(
+            "5-3".parse::<DaySet>().unwrap_err().to_string(),
+            "invalid day selection `5-3`: range is empty"
+        );
+    }
+
+    #[test]
+    fn formats_compactly() {
+        let set: DaySet = [day!(1), day!(2), day!(3), day!(5), day!(7), day!(8)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.to_string(), "1-3,5,7-8");
+        assert!(set.contains(day!(5)));
+        assert!(!set.contains(day!(6)));
+    }
+}
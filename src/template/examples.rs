@@ -0,0 +1,279 @@
+/// Module that checks solutions against the example files in `data/examples`.
+///
+/// Every example file `DD.txt` or `DD-N.txt` may have a sidecar file `DD.answers` or `DD-N.answers`
+/// that declares the expected answers, one `part_one: <answer>` or `part_two: <answer>` per line.
+/// It may also declare parameters that differ between the examples and the real input, such as
+/// the size of a grid, as `param <name>: <value>`. Lines starting with `#` are comments.
+use std::{cell::RefCell, collections::BTreeMap, env, fmt::Display, fs, path::Path, str::FromStr};
+
+use crate::template::Day;
+
+/// Expected answers of an example file. Parts without an answer are not checked.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+    /// Parameters of the example, see [`param`].
+    pub params: BTreeMap<String, String>,
+}
+
+impl Answers {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once(':')
+                .ok_or(format!("line {}: expected `part_one: <answer>`.", i + 1))?;
+
+            let (key, value) = (key.trim(), value.trim());
+            if value.is_empty() {
+                return Err(format!("line {}: `{key}` has no answer.", i + 1));
+            }
+
+            let declared_before = match key {
+                "part_one" => answers.part_one.replace(value.into()).is_some(),
+                "part_two" => answers.part_two.replace(value.into()).is_some(),
+                key => match key.strip_prefix("param ").map(str::trim) {
+                    Some(name) if !name.is_empty() => answers
+                        .params
+                        .insert(name.into(), value.into())
+                        .is_some(),
+                    _ => {
+                        return Err(format!(
+                            "line {}: unknown key `{key}`, expected `part_one`, `part_two` or `param <name>`.",
+                            i + 1
+                        ))
+                    }
+                },
+            };
+
+            if declared_before {
+                return Err(format!("line {}: `{key}` is declared twice.", i + 1));
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+/// An example file of a day together with its declared answers.
+#[derive(Debug)]
+pub struct Example {
+    /// File name of the example, e.g. `03-2.txt`.
+    pub name: String,
+    pub input: String,
+    pub answers: Answers,
+}
+
+/// Reads all example files of `day` from `dir`, ordered by name.
+pub fn read_examples(dir: &Path, day: Day) -> Result<Vec<Example>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| {
+            name.strip_suffix(".txt").is_some_and(|stem| {
+                stem == day.to_string()
+                    || stem
+                        .strip_prefix(&format!("{day}-"))
+                        .is_some_and(|n| n.parse::<u8>().is_ok())
+            })
+        })
+        .collect();
+    names.sort();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let path = dir.join(&name);
+            let input = fs::read_to_string(&path).map_err(|e| format!("{name}: {e}"))?;
+
+            let answers_path = path.with_extension("answers");
+            let answers = match fs::read_to_string(&answers_path) {
+                Ok(answers) => answers.parse().map_err(|e| {
+                    format!(
+                        "{}: {e}",
+                        answers_path.file_name().unwrap().to_string_lossy()
+                    )
+                })?,
+                Err(_) => Answers::default(),
+            };
+
+            Ok(Example {
+                name,
+                input,
+                answers,
+            })
+        })
+        .collect()
+}
+
+thread_local! {
+    static PARAMS: RefCell<BTreeMap<String, String>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// The parameter `name` declared by the example file that is being checked, or `None` if it
+/// declares none or the solution runs on any other input. Solutions fall back to the value of
+/// the real input, e.g. `param("size").map_or(71, |size| size.parse().unwrap())`.
+pub fn param(name: &str) -> Option<String> {
+    PARAMS.with(|params| params.borrow().get(name).cloned())
+}
+
+/// Calls `solve` on the input of `example` with the parameters of the example in place.
+fn solve_example<T>(example: &Example, solve: impl Fn(&str) -> Option<T>) -> Option<T> {
+    PARAMS.with(|params| params.replace(example.answers.params.clone()));
+    let answer = solve(&example.input);
+    PARAMS.with(|params| params.take());
+    answer
+}
+
+/// Runs `solve` against every example file of `day` that declares an answer for `part`,
+/// panicking with a list of all mismatches.
+pub fn check<T: Display>(day: Day, part: u8, solve: impl Fn(&str) -> Option<T>) {
+    let dir = env::current_dir().unwrap().join("data").join("examples");
+    let examples = read_examples(&dir, day).unwrap_or_else(|e| panic!("{e}"));
+    let failures = mismatches(&examples, part, solve);
+
+    assert!(
+        failures.is_empty(),
+        "day {day} part {part} is wrong for examples:\n{}",
+        failures.join("\n")
+    );
+}
+
+fn mismatches<T: Display>(
+    examples: &[Example],
+    part: u8,
+    solve: impl Fn(&str) -> Option<T>,
+) -> Vec<String> {
+    examples
+        .iter()
+        .filter_map(|example| {
+            let expected = example.answers.get(part)?;
+            let actual = solve_example(example, &solve).map(|answer| answer.to_string());
+
+            (actual.as_deref() != Some(expected)).then(|| {
+                format!(
+                    "{}: expected {expected}, got {}",
+                    example.name,
+                    actual.as_deref().unwrap_or("None")
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{mismatches, param, read_examples, Answers, Example};
+    use crate::day;
+    use std::{env, fs};
+
+    #[test]
+    fn parses_answers() {
+        let answers: Answers = "# from the puzzle text\npart_one: 11\n\npart_two:  abc,def \n"
+            .parse()
+            .unwrap();
+        assert_eq!(answers.get(1), Some("11"));
+        assert_eq!(answers.get(2), Some("abc,def"));
+
+        let answers: Answers = "# part_one: 1".parse().unwrap();
+        assert_eq!(answers, Answers::default());
+
+        let answers: Answers = "part_one: 12\nparam room: 11,7".parse().unwrap();
+        assert_eq!(answers.params.get("room").map(String::as_str), Some("11,7"));
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        assert!("part_one 11".parse::<Answers>().is_err());
+        assert!("part_three: 11".parse::<Answers>().is_err());
+        assert!("part_one: 11\npart_one: 12".parse::<Answers>().is_err());
+        assert!("part_two:".parse::<Answers>().is_err());
+        assert!("param: 1".parse::<Answers>().is_err());
+        assert!("param room:".parse::<Answers>().is_err());
+        assert!("param room: 1\nparam room: 2".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn reads_examples_of_a_day() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (name, contents) in [
+            ("03.txt", "a"),
+            ("03.answers", "part_one: 1"),
+            ("03-2.txt", "b"),
+            ("03-2.answers", "part_two: 2"),
+            ("03-notes.txt", "c"),
+            ("13.txt", "d"),
+        ] {
+            fs::write(dir.join(name), contents).unwrap();
+        }
+
+        let examples = read_examples(&dir, day!(3)).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "03-2.txt");
+        assert_eq!(examples[0].answers.get(2), Some("2"));
+        assert_eq!(examples[1].name, "03.txt");
+        assert_eq!(examples[1].input, "a");
+        assert_eq!(examples[1].answers.get(1), Some("1"));
+    }
+
+    #[test]
+    fn lists_mismatches() {
+        let example = |name: &str, input: &str, answers: &str| Example {
+            name: name.into(),
+            input: input.into(),
+            answers: answers.parse().unwrap(),
+        };
+        let examples = [
+            example("01.txt", "1\n2\n3", "part_one: 3\npart_two: 6"),
+            example("01-2.txt", "1\n2", "part_one: 3"),
+            example("01-3.txt", "", "part_one: 0"),
+        ];
+        let count = |input: &str| Some(input.lines().count()).filter(|n| *n > 0);
+
+        assert_eq!(
+            mismatches(&examples, 1, count),
+            vec![
+                "01-2.txt: expected 3, got 2",
+                "01-3.txt: expected 0, got None"
+            ]
+        );
+        assert!(mismatches(&examples, 2, |_| Some(6)).is_empty());
+    }
+
+    #[test]
+    fn passes_params_to_the_solution() {
+        let examples = [Example {
+            name: "14.txt".into(),
+            input: "".into(),
+            answers: "part_one: 11,7\nparam room: 11,7".parse().unwrap(),
+        }];
+        let room = |_: &str| Some(param("room").unwrap_or("101,103".into()));
+
+        assert!(mismatches(&examples, 1, room).is_empty());
+        assert_eq!(room(""), Some("101,103".into()));
+    }
+}
@@ -0,0 +1,313 @@
+/// Module that describes the environment benchmarks were measured in.
+use std::{collections::HashMap, env, fmt::Display, fs, process::Command, thread};
+
+use tinyjson::JsonValue;
+
+/// Describes the machine and build configuration of a benchmark run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub cpu: String,
+    pub cores: usize,
+    pub rustc: String,
+    pub target: String,
+    pub profile: String,
+    pub opt_level: String,
+    pub features: Vec<String>,
+    pub rustflags: Option<String>,
+}
+
+impl Fingerprint {
+    /// Detects the fingerprint of solutions that are built with `profile` and `features`
+    /// on this machine.
+    pub fn detect(profile: &str, features: &[&str]) -> Self {
+        let rustc = Command::new("rustc")
+            .arg("-vV")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+            .unwrap_or_default();
+
+        Self {
+            cpu: cpu_model().unwrap_or_else(|| "unknown".into()),
+            cores: thread::available_parallelism().map_or(1, usize::from),
+            rustc: rustc.lines().next().unwrap_or("unknown").into(),
+            target: rustc
+                .lines()
+                .find_map(|line| line.strip_prefix("host: "))
+                .unwrap_or("unknown")
+                .into(),
+            profile: profile.into(),
+            opt_level: opt_level(profile),
+            features: features.iter().map(ToString::to_string).collect(),
+            rustflags: env::var("RUSTFLAGS").ok().filter(|flags| !flags.is_empty()),
+        }
+    }
+
+    /// Lists the fields that differ from `other` as `(name, self, other)`.
+    pub fn differences(&self, other: &Self) -> Vec<(&'static str, String, String)> {
+        let fields = |f: &Self| {
+            [
+                ("cpu", f.cpu.clone()),
+                ("cores", f.cores.to_string()),
+                ("rustc", f.rustc.clone()),
+                ("target", f.target.clone()),
+                ("profile", f.profile.clone()),
+                ("opt-level", f.opt_level.clone()),
+                ("features", f.features.join(",")),
+                ("RUSTFLAGS", f.rustflags.clone().unwrap_or_default()),
+            ]
+        };
+
+        fields(self)
+            .into_iter()
+            .zip(fields(other))
+            .filter(|((_, a), (_, b))| a != b)
+            .map(|((name, a), (_, b))| (name, a, b))
+            .collect()
+    }
+}
+
+impl Display for Fingerprint {
+    /// Formats a one-line summary, e.g. `AMD Ryzen 7 5800X (16 cores) · rustc 1.83.0 · x86_64-unknown-linux-gnu · release (opt-level 3)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} {}) · {} · {} · {} (opt-level {})",
+            self.cpu,
+            self.cores,
+            if self.cores == 1 { "core" } else { "cores" },
+            self.rustc.split(" (").next().unwrap_or(&self.rustc),
+            self.target,
+            self.profile,
+            self.opt_level
+        )?;
+
+        if !self.features.is_empty() {
+            write!(f, " · features: {}", self.features.join(", "))?;
+        }
+        if let Some(rustflags) = &self.rustflags {
+            write!(f, " · RUSTFLAGS: {rustflags}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+        return cpuinfo
+            .lines()
+            .find(|line| line.starts_with("model name"))
+            .and_then(|line| line.split_once(':'))
+            .map(|(_, model)| model.trim().to_string());
+    }
+
+    if let Ok(identifier) = env::var("PROCESSOR_IDENTIFIER") {
+        return Some(identifier);
+    }
+
+    Command::new("sysctl")
+        .args(["-n", "machdep.cpu.brand_string"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Resolves the `opt-level` of `profile`, following the precedence of cargo:
+/// environment variable, then `Cargo.toml`, then the built-in default.
+fn opt_level(profile: &str) -> String {
+    let env_var = format!(
+        "CARGO_PROFILE_{}_OPT_LEVEL",
+        profile.to_uppercase().replace('-', "_")
+    );
+    if let Ok(level) = env::var(env_var) {
+        return level;
+    }
+
+    let manifest = fs::read_to_string("Cargo.toml").unwrap_or_default();
+    opt_level_from_manifest(&manifest, profile).unwrap_or_else(|| {
+        if profile == "dev" || profile == "test" {
+            "0".into()
+        } else {
+            "3".into()
+        }
+    })
+}
+
+fn opt_level_from_manifest(manifest: &str, profile: &str) -> Option<String> {
+    let header = format!("[profile.{profile}]");
+    manifest
+        .lines()
+        .skip_while(|line| line.trim() != header)
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "opt-level").then(|| value.trim().trim_matches('"').to_string())
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Fingerprint> for JsonValue {
+    fn from(value: &Fingerprint) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        // NOTE: core counts are small integers.
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("target".into(), JsonValue::String(value.target.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "opt_level".into(),
+            JsonValue::String(value.opt_level.clone()),
+        );
+        map.insert(
+            "features".into(),
+            JsonValue::Array(
+                value
+                    .features
+                    .iter()
+                    .cloned()
+                    .map(JsonValue::String)
+                    .collect(),
+            ),
+        );
+        map.insert(
+            "rustflags".into(),
+            value
+                .rustflags
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Fingerprint {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected fingerprint to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>().cloned())
+                .ok_or(format!("Expected fingerprint.{key} to be a string."))
+        };
+
+        // NOTE: core counts are small, positive integers.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as usize)
+            .ok_or("Expected fingerprint.cores to be a number.")?;
+
+        let features = json
+            .get("features")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected fingerprint.features to be an array.")?
+            .iter()
+            .map(|v| v.get::<String>().cloned())
+            .collect::<Option<_>>()
+            .ok_or("Expected fingerprint.features to contain strings.")?;
+
+        let rustflags = json
+            .get("rustflags")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected fingerprint.rustflags to be null or string.")?
+            .cloned();
+
+        Ok(Fingerprint {
+            cpu: string("cpu")?,
+            cores,
+            rustc: string("rustc")?,
+            target: string("target")?,
+            profile: string("profile")?,
+            opt_level: string("opt_level")?,
+            features,
+            rustflags,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{opt_level_from_manifest, Fingerprint};
+    use tinyjson::JsonValue;
+
+    fn get_mock_fingerprint() -> Fingerprint {
+        Fingerprint {
+            cpu: "AMD Ryzen 7 5800X 8-Core Processor".into(),
+            cores: 16,
+            rustc: "rustc 1.83.0 (90b35a623 2024-11-26)".into(),
+            target: "x86_64-unknown-linux-gnu".into(),
+            profile: "release".into(),
+            opt_level: "3".into(),
+            features: vec![],
+            rustflags: None,
+        }
+    }
+
+    #[test]
+    fn roundtrips_fingerprints() {
+        let mut fingerprint = get_mock_fingerprint();
+        fingerprint.rustflags = Some("-C target-cpu=native".into());
+        fingerprint.features = vec!["today".into()];
+
+        let json = JsonValue::from(&fingerprint);
+        assert_eq!(Fingerprint::try_from(&json).unwrap(), fingerprint);
+    }
+
+    #[test]
+    fn lists_differences() {
+        let a = get_mock_fingerprint();
+        let mut b = get_mock_fingerprint();
+        assert!(a.differences(&b).is_empty());
+
+        b.cores = 8;
+        b.rustflags = Some("-C target-cpu=native".into());
+        assert_eq!(
+            a.differences(&b),
+            vec![
+                ("cores", "16".into(), "8".into()),
+                ("RUSTFLAGS", String::new(), "-C target-cpu=native".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_summary() {
+        let mut fingerprint = get_mock_fingerprint();
+        assert_eq!(
+            fingerprint.to_string(),
+            "AMD Ryzen 7 5800X 8-Core Processor (16 cores) · rustc 1.83.0 · x86_64-unknown-linux-gnu · release (opt-level 3)"
+        );
+
+        fingerprint.rustflags = Some("-C target-cpu=native".into());
+        assert!(fingerprint
+            .to_string()
+            .ends_with(" · RUSTFLAGS: -C target-cpu=native"));
+    }
+
+    #[test]
+    fn reads_opt_level_from_manifest() {
+        let manifest = "[profile.dhat]\ninherits = \"release\"\n\n[profile.release]\nopt-level = 2\nlto = true\n\n[features]\nopt-level = 1";
+        assert_eq!(
+            opt_level_from_manifest(manifest, "release"),
+            Some("2".into())
+        );
+        assert_eq!(opt_level_from_manifest(manifest, "dhat"), None);
+        assert_eq!(opt_level_from_manifest(manifest, "bench"), None);
+    }
+}
@@ -0,0 +1,54 @@
+/// Module that selects the input a solution runs against.
+use std::{env, fs, process};
+
+use crate::template::{read_file, Day};
+use crate::util::prop::{Rng, DEFAULT_SEED};
+
+/// Produces a valid puzzle input of roughly `size` bytes.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// Reads the input of a solution binary. The following arguments change where it comes from:
+///  1. `--input <path>` reads the input from `path` instead of `data/inputs`.
+///  2. `--generate <size> [--seed <seed>]` prints an input created by `generator` and exits.
+pub fn read(day: Day, generator: Option<Generator>) -> String {
+    let args: Vec<String> = env::args().collect();
+    let value = |flag: &str| {
+        let i = args.iter().position(|arg| arg == flag)?;
+        match args.get(i + 1) {
+            Some(value) => Some(value.clone()),
+            None => {
+                eprintln!("Missing value for {flag}.");
+                process::exit(1);
+            }
+        }
+    };
+
+    if let Some(size) = value("--generate") {
+        let Ok(size) = size.parse::<usize>() else {
+            eprintln!("Unexpected command-line input. Format: --generate <size> [--seed <seed>]");
+            process::exit(1);
+        };
+        let Some(generator) = generator else {
+            eprintln!("Day {day} has no input generator.");
+            process::exit(1);
+        };
+
+        let seed = value("--seed").map_or(DEFAULT_SEED, |seed| {
+            seed.parse().unwrap_or_else(|_| {
+                eprintln!("Unexpected command-line input. Format: --seed <number>");
+                process::exit(1);
+            })
+        });
+
+        print!("{}", generator(&mut Rng::new(seed), size));
+        process::exit(0);
+    }
+
+    match value("--input") {
+        Some(path) => fs::read_to_string(&path).unwrap_or_else(|e| {
+            eprintln!("Could not open input file \"{path}\": {e}");
+            process::exit(1);
+        }),
+        None => read_file("inputs", day),
+    }
+}
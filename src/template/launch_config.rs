@@ -0,0 +1,338 @@
+/// Module that adds per-day debug configurations to `.vscode/launch.json`.
+/// `launch.json` allows comments and trailing commas, so instead of re-serializing the document
+/// this module locates the `configurations` array and only inserts text into it. Everything else,
+/// including user edits to previously generated entries, is left as-is.
+use std::ops::Range;
+
+use crate::template::Day;
+
+pub static LAUNCH_CONFIG_PATH: &str = ".vscode/launch.json";
+
+static DEFAULT_INDENT: &str = "    ";
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Parser(String),
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open(u8),
+    Close(u8),
+    Comma,
+    Colon,
+    String(String),
+    Literal,
+}
+
+/// Splits a JSONC document into tokens with their byte ranges, skipping whitespace and comments.
+fn tokenize(s: &str) -> Result<Vec<(Token, Range<usize>)>, Error> {
+    let bytes = s.as_bytes();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = s[i + 2..]
+                    .find("*/")
+                    .ok_or_else(|| Error::Parser("unterminated block comment.".into()))?;
+                i += end + 4;
+                continue;
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                if i >= bytes.len() {
+                    return Err(Error::Parser("unterminated string.".into()));
+                }
+                i += 1;
+                tokens.push((Token::String(s[start + 1..i - 1].to_string()), start..i));
+                continue;
+            }
+            b'{' | b'[' => tokens.push((Token::Open(bytes[i]), start..i + 1)),
+            b'}' | b']' => tokens.push((Token::Close(bytes[i]), start..i + 1)),
+            b',' => tokens.push((Token::Comma, start..i + 1)),
+            b':' => tokens.push((Token::Colon, start..i + 1)),
+            b if b.is_ascii_whitespace() => {}
+            _ => {
+                while i < bytes.len()
+                    && !bytes[i].is_ascii_whitespace()
+                    && !b"{}[],:/\"".contains(&bytes[i])
+                {
+                    i += 1;
+                }
+                tokens.push((Token::Literal, start..i));
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// Location of the top-level `configurations` array.
+struct Configurations {
+    /// Token index of the opening bracket.
+    open: usize,
+    /// Token index of the closing bracket.
+    close: usize,
+    /// Names of all configurations in the array.
+    names: Vec<String>,
+}
+
+fn locate_configurations(tokens: &[(Token, Range<usize>)]) -> Result<Configurations, Error> {
+    let mut depth = 0;
+    let mut open = None;
+
+    for (i, (token, _)) in tokens.iter().enumerate() {
+        match token {
+            Token::Open(_) => depth += 1,
+            Token::Close(_) => depth -= 1,
+            Token::String(key) if depth == 1 && key == "configurations" => {
+                if let (Some((Token::Colon, _)), Some((Token::Open(b'['), _))) =
+                    (tokens.get(i + 1), tokens.get(i + 2))
+                {
+                    open = Some(i + 2);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let open = open.ok_or_else(|| Error::Parser("could not find `configurations`.".into()))?;
+
+    let mut depth = 0;
+    let mut names = vec![];
+
+    for (i, (token, _)) in tokens.iter().enumerate().skip(open) {
+        match token {
+            Token::Open(_) => depth += 1,
+            Token::Close(_) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(Configurations {
+                        open,
+                        close: i,
+                        names,
+                    });
+                }
+            }
+            Token::String(key) if depth == 2 && key == "name" => {
+                if let (Some((Token::Colon, _)), Some((Token::String(name), _))) =
+                    (tokens.get(i + 1), tokens.get(i + 2))
+                {
+                    names.push(name.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Err(Error::Parser("unterminated `configurations` array.".into()))
+}
+
+/// Name of the configuration that debugs the solution binary of `day`.
+pub fn solution_config_name(day: Day) -> String {
+    format!("Debug day {day}")
+}
+
+/// Name of the configuration that debugs the unit tests of `day`.
+pub fn tests_config_name(day: Day) -> String {
+    format!("Debug unit tests for day {day}")
+}
+
+fn render_entry(name: &str, cargo_args: &[&str], filter: Option<Day>, indent: &str) -> String {
+    let i1 = DEFAULT_INDENT;
+    let i2 = DEFAULT_INDENT.repeat(2);
+    let i3 = DEFAULT_INDENT.repeat(3);
+
+    let mut lines = vec![
+        "{".to_string(),
+        format!("{i1}\"type\": \"lldb\","),
+        format!("{i1}\"request\": \"launch\","),
+        format!("{i1}\"name\": \"{name}\","),
+        format!("{i1}\"cargo\": {{"),
+        format!("{i2}\"args\": ["),
+    ];
+
+    for (i, arg) in cargo_args.iter().enumerate() {
+        let comma = if i + 1 < cargo_args.len() { "," } else { "" };
+        lines.push(format!("{i3}\"{arg}\"{comma}"));
+    }
+
+    match filter {
+        Some(day) => {
+            lines.push(format!("{i2}],"));
+            lines.push(format!(
+                "{i2}\"filter\": {{ \"name\": \"{day}\", \"kind\": \"bin\" }}"
+            ));
+        }
+        None => lines.push(format!("{i2}]")),
+    }
+
+    lines.extend([
+        format!("{i1}}},"),
+        format!("{i1}\"args\": [],"),
+        format!("{i1}\"cwd\": \"${{workspaceFolder}}\""),
+        "}".to_string(),
+    ]);
+
+    // the first line is placed after existing indentation by the caller.
+    lines.join(&format!("\n{indent}"))
+}
+
+fn line_indent(s: &str, pos: usize) -> &str {
+    let line_start = s[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let line = &s[line_start..];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Returns `launch.json` contents that include debug configurations for `day`.
+/// Returns `None` if both configurations are already present.
+pub fn update(contents: &str, day: Day) -> Result<Option<String>, Error> {
+    let tokens = tokenize(contents)?;
+    let configurations = locate_configurations(&tokens)?;
+
+    let solution_name = solution_config_name(day);
+    let tests_name = tests_config_name(day);
+    let bin_arg = format!("--bin={day}");
+
+    let missing: Vec<(&str, Vec<&str>, Option<Day>)> = [
+        (
+            solution_name.as_str(),
+            vec!["build", &bin_arg, "--package=advent_of_code"],
+            None,
+        ),
+        (
+            tests_name.as_str(),
+            vec!["test", "--no-run", &bin_arg, "--package=advent_of_code"],
+            Some(day),
+        ),
+    ]
+    .into_iter()
+    .filter(|(name, _, _)| !configurations.names.iter().any(|n| n == name))
+    .collect();
+
+    if missing.is_empty() {
+        return Ok(None);
+    }
+
+    let array_start = tokens[configurations.open].1.start;
+    let outer_indent = line_indent(contents, array_start);
+    let is_empty = configurations.close == configurations.open + 1;
+
+    // match the indentation of existing entries if there are any.
+    let indent = if is_empty {
+        format!("{outer_indent}{DEFAULT_INDENT}")
+    } else {
+        line_indent(contents, tokens[configurations.open + 1].1.start).to_string()
+    };
+
+    let last = &tokens[configurations.close - 1];
+    let needs_comma = !matches!(last.0, Token::Comma | Token::Open(b'['));
+
+    let mut insertion = String::new();
+    for (i, (name, args, filter)) in missing.iter().enumerate() {
+        if i > 0 || needs_comma {
+            insertion.push(',');
+        }
+        insertion.push('\n');
+        insertion.push_str(&indent);
+        insertion.push_str(&render_entry(name, args, *filter, &indent));
+    }
+
+    if is_empty {
+        insertion.push('\n');
+        insertion.push_str(outer_indent);
+    }
+
+    let mut updated = contents.to_string();
+    updated.insert_str(last.1.end, &insertion);
+    Ok(Some(updated))
+}
+
+/// Returns a new `launch.json` that only contains the debug configurations for `day`.
+pub fn create(day: Day) -> String {
+    let skeleton = "{\n    \"version\": \"0.2.0\",\n    \"configurations\": []\n}\n";
+    update(skeleton, day)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| skeleton.to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{create, locate_configurations, tokenize, update};
+    use crate::day;
+
+    fn names(contents: &str) -> Vec<String> {
+        locate_configurations(&tokenize(contents).unwrap())
+            .unwrap()
+            .names
+    }
+
+    #[test]
+    fn creates_new_launch_config() {
+        let contents = create(day!(10));
+        assert_eq!(
+            names(&contents),
+            vec!["Debug day 10", "Debug unit tests for day 10"]
+        );
+        assert!(contents.contains("\"--bin=10\""));
+        assert!(contents.ends_with("    ]\n}\n"));
+    }
+
+    #[test]
+    fn appends_to_existing_configurations() {
+        let contents = r#"{
+    // a comment with "configurations": [
+    "version": "0.2.0",
+    "configurations": [
+        {
+            "type": "lldb",
+            "name": "My config", /* keep me */
+            "args": [],
+        },
+    ]
+}"#;
+        let updated = update(contents, day!(3)).unwrap().unwrap();
+        assert!(updated.contains("// a comment with \"configurations\": ["));
+        assert!(updated.contains("\"name\": \"My config\", /* keep me */"));
+        assert_eq!(
+            names(&updated),
+            vec!["My config", "Debug day 03", "Debug unit tests for day 03"]
+        );
+        assert!(updated.contains("\n        {\n            \"type\": \"lldb\",\n            \"request\": \"launch\",\n            \"name\": \"Debug day 03\""));
+    }
+
+    #[test]
+    fn keeps_existing_day_configurations() {
+        let contents = create(day!(10));
+        assert_eq!(update(&contents, day!(10)), Ok(None));
+
+        let edited = contents.replace("\"cwd\"", "\"stopOnEntry\": true, \"cwd\"");
+        assert_eq!(update(&edited, day!(10)), Ok(None));
+
+        let updated = update(&edited, day!(11)).unwrap().unwrap();
+        assert!(updated.contains("\"stopOnEntry\": true"));
+        assert_eq!(names(&updated).len(), 4);
+    }
+
+    #[test]
+    fn errors_without_configurations() {
+        assert!(update("{ \"version\": \"0.2.0\" }", day!(1)).is_err());
+        assert!(update("{ \"configurations\": [ ", day!(1)).is_err());
+    }
+}
@@ -1,15 +1,32 @@
 use std::{env, fs};
 
 pub mod aoc_cli;
+pub mod cli;
 pub mod commands;
+pub mod examples;
+pub mod input;
+pub mod puzzle;
 pub mod runner;
+#[cfg(feature = "today")]
+pub mod unlock;
 
 pub use day::*;
+pub use day_set::*;
 
+mod benchmark_chart;
+mod completions;
 mod day;
+mod day_set;
+mod fingerprint;
+mod launch_config;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
+mod scale;
+mod solution_template;
 mod timings;
+mod timings_export;
+mod timings_history;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -39,19 +56,30 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// An optional `generator = <fn>` registers an [`input::Generator`] used by `cargo scale`.
+/// In tests, each part is checked against the example answers, see [`examples`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, None, [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, None, [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, None, [part_two, 2]);
+    };
+    ($day:expr, generator = $generator:path) => {
+        $crate::solution!(@impl $day, Some($generator), [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, generator = $generator:path) => {
+        $crate::solution!(@impl $day, Some($generator), [part_one, 1]);
+    };
+    ($day:expr, 2, generator = $generator:path) => {
+        $crate::solution!(@impl $day, Some($generator), [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $generator:expr, $( [$func:ident, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -61,8 +89,19 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+            let input = $crate::template::input::read(DAY, $generator);
             $( run_part($func, &input, DAY, $part); )*
         }
+
+        /// Checks every part against the answers declared for `data/examples`.
+        #[cfg(test)]
+        mod examples {
+            $(
+                #[test]
+                fn $func() {
+                    $crate::template::examples::check(super::DAY, $part, super::$func);
+                }
+            )*
+        }
     };
 }
@@ -0,0 +1,51 @@
+/// Helpers that extract information from puzzle descriptions downloaded by aoc-cli.
+use std::fs;
+
+use crate::template::{aoc_cli, Day};
+
+static ANSWER_MARKER: &str = "Your puzzle answer was";
+static COMPLETE_MARKER: &str = "Both parts of this puzzle are complete";
+
+/// Returns the number of stars (0 to 2) collected for `day`, based on its downloaded puzzle description.
+pub fn read_stars(day: Day) -> u8 {
+    fs::read_to_string(aoc_cli::get_puzzle_path(day)).map_or(0, |puzzle| parse_stars(&puzzle))
+}
+
+/// Counts the accepted answers in a puzzle description.
+/// The last puzzle of an event only has one answer, its second star is awarded when the description
+/// states that both parts are complete.
+pub fn parse_stars(puzzle: &str) -> u8 {
+    let answers = puzzle.matches(ANSWER_MARKER).count();
+    if puzzle.contains(COMPLETE_MARKER) {
+        2
+    } else {
+        // NOTE: a puzzle description contains at most two answers.
+        u8::try_from(answers.min(2)).unwrap()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_stars;
+
+    #[test]
+    fn counts_answers() {
+        assert_eq!(parse_stars("\\--- Day 1: Historian Hysteria ---"), 0);
+        assert_eq!(
+            parse_stars("...\n\nYour puzzle answer was `1319616`.\n\n"),
+            1
+        );
+        assert_eq!(
+            parse_stars("Your puzzle answer was `1`.\n\nYour puzzle answer was `2`.\n\nBoth parts of this puzzle are complete! They provide two gold stars: \\*\\*"),
+            2
+        );
+    }
+
+    #[test]
+    fn counts_last_day_as_complete() {
+        assert_eq!(
+            parse_stars("Your puzzle answer was `1`.\n\nBoth parts of this puzzle are complete!"),
+            2
+        );
+    }
+}
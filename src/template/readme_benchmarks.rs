@@ -1,13 +1,15 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, path::Path, str::FromStr};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::timings::{format_duration, PartTiming, Timing, Timings};
+use crate::template::{aoc_cli, benchmark_chart, Day};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_NAME: &str = "benchmarking table";
+
+/// Width of the bars rendered by the `bar` column, in characters.
+const BAR_WIDTH: usize = 16;
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -20,9 +22,23 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A table in the readme, delimited by markers like `<!--- benchmarking table sort=time --->`.
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
+    /// The full marker, which is kept when the table is replaced.
+    pub marker: String,
+    /// Everything between the marker name and the end of the comment.
+    pub options: String,
 }
 
 #[must_use]
@@ -30,100 +46,436 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates all tables marked with `<!--- {name} [options] --->` in `readme`, ordered by position.
+/// A table is either a single marker that has not been filled yet, or a pair of identical markers
+/// that surround the previously rendered content.
+pub fn locate_tables(readme: &str, name: &str) -> Result<Vec<TablePosition>, Error> {
+    let prefix = format!("<!--- {name}");
 
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+    let markers: Vec<(usize, &str)> = readme
+        .match_indices(&prefix)
+        .filter_map(|(start, _)| {
+            // NOTE: markers have to end on the same line, options are separated by whitespace.
+            let line = readme[start + prefix.len()..].lines().next()?;
+            let end = line.find("--->")?;
+            if end > 0 && !line.starts_with(' ') {
+                return None;
+            }
+            Some((start, &readme[start..start + prefix.len() + end + 4]))
+        })
+        .collect();
+
+    let mut tables: Vec<TablePosition> = vec![];
+
+    for &(start, marker) in &markers {
+        let occurrences: Vec<_> = markers.iter().filter(|(_, m)| *m == marker).collect();
+
+        if occurrences.len() > 2 {
+            return Err(Error::Parser(format!(
+                "{marker}: too many occurences of marker in README."
+            )));
+        }
+
+        // NOTE: the second occurrence closes the table that starts at the first one.
+        if occurrences[0].0 != start {
+            continue;
+        }
+
+        let pos_end = occurrences
+            .last()
+            .map(|(pos, m)| pos + m.len())
+            .unwrap_or(start + marker.len());
+
+        tables.push(TablePosition {
+            pos_start: start,
+            pos_end,
+            marker: marker.into(),
+            options: marker[prefix.len()..marker.len() - 4].trim().into(),
+        });
+    }
+
+    if tables.is_empty() {
+        return Err(Error::Parser(format!(
+            "Could not find marker `{prefix} --->` in README."
+        )));
+    }
+
+    if tables
+        .windows(2)
+        .any(|pair| pair[1].pos_start < pair[0].pos_end)
+    {
+        return Err(Error::Parser(format!(
+            "{prefix} --->: tables must not overlap."
+        )));
+    }
+
+    Ok(tables)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Column {
+    Day,
+    Part1,
+    Part2,
+    Total,
+    Bar,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SortKey {
+    Day,
+    Time,
+}
+
+/// A fixed unit for the values in a table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Unit {
+    Nanos,
+    Micros,
+    Millis,
+    Seconds,
+}
+
+impl Unit {
+    fn format(self, nanos: f64) -> String {
+        let (scale, suffix) = match self {
+            Unit::Nanos => (1.0, "ns"),
+            Unit::Micros => (1e3, "µs"),
+            Unit::Millis => (1e6, "ms"),
+            Unit::Seconds => (1e9, "s"),
+        };
+        format!("{:.2}{suffix}", nanos / scale)
     }
+}
+
+/// Options that can be appended to a table marker, e.g. `columns=day,p1,p2,total sort=-time`.
+#[derive(Clone, Debug, PartialEq)]
+struct TableOptions {
+    columns: Vec<Column>,
+    sort: SortKey,
+    descending: bool,
+    /// If not set, every value is formatted in its own unit.
+    unit: Option<Unit>,
+    highlight_slowest: bool,
+    /// Level of the table heading, `0` omits it.
+    level: usize,
+    /// If set, the table is only updated while `AOC_YEAR` matches.
+    year: Option<u16>,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Day, Column::Part1, Column::Part2],
+            sort: SortKey::Day,
+            descending: false,
+            unit: None,
+            highlight_slowest: false,
+            level: 2,
+            year: None,
+        }
+    }
+}
+
+impl FromStr for TableOptions {
+    type Err = Error;
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut options = TableOptions::default();
+        let invalid = |option: &str, reason: &str| {
+            Error::Parser(format!("invalid table option `{option}`: {reason}"))
+        };
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+        for option in s.split_whitespace() {
+            let (key, value) = option
+                .split_once('=')
+                .ok_or_else(|| invalid(option, "expected `key=value`"))?;
 
-    Ok(TablePosition { pos_start, pos_end })
+            match key {
+                "columns" => {
+                    options.columns = value
+                        .split(',')
+                        .map(|column| match column {
+                            "day" => Ok(Column::Day),
+                            "p1" => Ok(Column::Part1),
+                            "p2" => Ok(Column::Part2),
+                            "total" => Ok(Column::Total),
+                            "bar" => Ok(Column::Bar),
+                            "memory" => Err(invalid(
+                                option,
+                                "timings do not record memory usage, profile it with `cargo solve <day> --dhat`",
+                            )),
+                            _ => Err(invalid(option, "expected day, p1, p2, total or bar")),
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+                "sort" => {
+                    options.descending = value.starts_with('-');
+                    options.sort = match value.trim_start_matches('-') {
+                        "day" => SortKey::Day,
+                        "time" => SortKey::Time,
+                        _ => return Err(invalid(option, "expected day or time")),
+                    };
+                }
+                "unit" => {
+                    options.unit = match value {
+                        "auto" => None,
+                        "ns" => Some(Unit::Nanos),
+                        "us" | "µs" => Some(Unit::Micros),
+                        "ms" => Some(Unit::Millis),
+                        "s" => Some(Unit::Seconds),
+                        _ => return Err(invalid(option, "expected auto, ns, us, ms or s")),
+                    };
+                }
+                "highlight" => {
+                    options.highlight_slowest = match value {
+                        "slowest" => true,
+                        "none" => false,
+                        _ => return Err(invalid(option, "expected slowest or none")),
+                    };
+                }
+                "level" => {
+                    options.level = value
+                        .parse()
+                        .ok()
+                        .filter(|level| *level <= 6)
+                        .ok_or_else(|| invalid(option, "expected a heading level from 0 to 6"))?;
+                }
+                "year" => {
+                    options.year = Some(
+                        value
+                            .parse()
+                            .map_err(|_| invalid(option, "expected a year"))?,
+                    );
+                }
+                _ => return Err(invalid(option, "unknown option")),
+            }
+        }
+
+        Ok(options)
+    }
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/* -------------------------------------------------------------------------- */
+
+fn format_value(nanos: f64, options: &TableOptions) -> String {
+    options
+        .unit
+        .map_or_else(|| format_duration(nanos), |unit| unit.format(nanos))
+}
+
+fn format_bar(nanos: f64, max_nanos: f64) -> String {
+    // NOTE: the ratio is within 0..=1, so the width is within 0..=BAR_WIDTH.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let filled = if max_nanos > 0.0 {
+        ((nanos / max_nanos) * BAR_WIDTH as f64).round() as usize
+    } else {
+        0
+    };
+    format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled))
+}
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+fn format_cell(column: Column, timing: &Timing, max_nanos: f64, options: &TableOptions) -> String {
+    let format_part = |part: &Option<PartTiming>| {
+        part.as_ref().map_or_else(
+            || "-".into(),
+            |p| format!("`{}`", format_value(p.nanos, options)),
+        )
+    };
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+    match column {
+        Column::Day => format!(
+            "[Day {}]({})",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            get_path_for_bin(timing.day)
+        ),
+        Column::Part1 => format_part(&timing.part_1),
+        Column::Part2 => format_part(&timing.part_2),
+        Column::Total => format!("`{}`", format_value(timing.total_nanos, options)),
+        Column::Bar => format_bar(timing.total_nanos, max_nanos),
+    }
+}
+
+fn construct_table(
+    marker: &str,
+    options: &TableOptions,
+    timings: &Timings,
+    chart: Option<&str>,
+) -> String {
+    let mut lines: Vec<String> = vec![marker.into()];
+
+    if options.level > 0 {
+        let title = options
+            .year
+            .map_or_else(|| "Benchmarks".into(), |year| format!("Benchmarks {year}"));
+        lines.push(format!("{} {title}", "#".repeat(options.level)));
+        lines.push(String::new());
+    }
+
+    if let Some(chart) = chart {
+        lines.push(format!("![Benchmark chart]({chart})"));
+        lines.push(String::new());
+    }
+
+    let header: Vec<&str> = options
+        .columns
+        .iter()
+        .map(|column| match column {
+            Column::Day => "Day",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Total => "Total",
+            Column::Bar => "",
+        })
+        .collect();
+    lines.push(format!("| {} |", header.join(" | ")));
+    lines.push(format!("|{}", " :---: |".repeat(options.columns.len())));
+
+    let mut data: Vec<&Timing> = timings.data.iter().collect();
+    match options.sort {
+        SortKey::Day => data.sort_by_key(|t| t.day),
+        SortKey::Time => data.sort_by(|a, b| a.total_nanos.total_cmp(&b.total_nanos)),
+    }
+    if options.descending {
+        data.reverse();
+    }
+
+    let max_nanos = data.iter().map(|t| t.total_nanos).fold(0.0, f64::max);
+    let slowest = data
+        .iter()
+        .max_by(|a, b| a.total_nanos.total_cmp(&b.total_nanos))
+        .map(|t| t.day);
+
+    for timing in data {
+        let highlight = options.highlight_slowest && slowest == Some(timing.day);
+        let cells: Vec<String> = options
+            .columns
+            .iter()
+            .map(|column| {
+                let cell = format_cell(*column, timing, max_nanos, options);
+                if highlight && *column != Column::Bar && cell != "-" {
+                    format!("**{cell}**")
+                } else {
+                    cell
+                }
+            })
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
+    let total_nanos: f64 = timings.data.iter().map(|t| t.total_nanos).sum();
+    let total = options.unit.map_or_else(
+        || format!("{:.2}ms", total_nanos / 1_000_000_f64),
+        |unit| unit.format(total_nanos),
+    );
+
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(format!("**Total: {total}**"));
+
+    if let Some(fingerprint) = &timings.fingerprint {
+        lines.push(String::new());
+        lines.push(format!("<sub>{fingerprint}</sub>"));
+    }
+
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+/// Renders every benchmark table in `s`. Tables for a `year` other than `current_year` are kept as they are.
+/// Tables link to a chart if `chart` is set or if they already did, returns the paths of the linked charts.
+fn update_content(
+    s: &mut String,
+    timings: &Timings,
+    current_year: Option<u16>,
+    chart: bool,
+) -> Result<Vec<String>, Error> {
+    let tables = locate_tables(s, MARKER_NAME)?;
+    let mut charts = vec![];
+
+    // NOTE: replace back to front, so that earlier positions stay valid.
+    for position in tables.iter().rev() {
+        let options: TableOptions = position.options.parse()?;
+        if options.year.is_some() && options.year != current_year {
+            continue;
+        }
+
+        let chart_path = benchmark_chart::chart_path(options.year);
+        let chart = chart || s[position.pos_start..position.pos_end].contains(&chart_path);
+
+        let table = construct_table(
+            &position.marker,
+            &options,
+            timings,
+            chart.then_some(chart_path.as_str()),
+        );
+        s.replace_range(position.pos_start..position.pos_end, &table);
+
+        if chart {
+            charts.push(chart_path);
+        }
+    }
+
+    Ok(charts)
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Updates the benchmark tables in the readme and renders the charts they link to.
+pub fn update(timings: Timings, chart: bool) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let charts = update_content(&mut readme, &timings, aoc_cli::get_year(), chart)?;
+
+    for chart in charts {
+        if let Some(parent) = Path::new(&chart).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&chart, benchmark_chart::render(&timings))?;
+    }
+
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{locate_tables, update_content, Error, TableOptions, MARKER_NAME};
+    use crate::{
+        day,
+        template::fingerprint::Fingerprint,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    static MARKER: &str = "<!--- benchmarking table --->";
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(PartTiming::new(10e6)),
+                    part_2: Some(PartTiming::new(20e6)),
+                    total_nanos: 3e+7,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(PartTiming::new(30e6)),
+                    part_2: Some(PartTiming::new(40e6)),
+                    total_nanos: 7e+7,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    part_1: Some(PartTiming::new(40e6)),
+                    part_2: Some(PartTiming::new(50e6)),
+                    total_nanos: 9e+7,
                 },
             ],
+            fingerprint: None,
         }
     }
 
@@ -131,28 +483,36 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), None, false).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), None, false).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), None, false).unwrap();
+        assert_eq!(s.contains("## Benchmarks"), true);
+    }
+
+    #[test]
+    fn updates_single_marker() {
+        let mut s = format!("foo\n{}\nbaz", MARKER);
+        update_content(&mut s, &get_mock_timings(), None, false).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), None, false).unwrap();
+        update_content(&mut s, &get_mock_timings(), None, false).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +520,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), None, false).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,10 +528,10 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +540,112 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_configured_benchmarks() {
+        let marker = "<!--- benchmarking table columns=day,total,bar sort=-time unit=ms highlight=slowest level=3 --->";
+        let mut s = marker.to_string();
+        update_content(&mut s, &get_mock_timings(), None, false).unwrap();
+        let expected = [
+            marker,
+            "### Benchmarks",
+            "",
+            "| Day | Total |  |",
+            "| :---: | :---: | :---: |",
+            "| **[Day 4](./src/bin/04.rs)** | **`90.00ms`** | ████████████████ |",
+            "| [Day 2](./src/bin/02.rs) | `70.00ms` | ████████████░░░░ |",
+            "| [Day 1](./src/bin/01.rs) | `30.00ms` | █████░░░░░░░░░░░ |",
+            "",
+            "**Total: 190.00ms**",
+            marker,
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn rejects_memory_column() {
+        let mut s = "<!--- benchmarking table columns=day,p1,memory --->".to_string();
+        let error = update_content(&mut s, &get_mock_timings(), None, false).unwrap_err();
+        assert!(
+            matches!(error, Error::Parser(message) if message.contains("do not record memory"))
+        );
+    }
+
+    #[test]
+    fn updates_tables_of_current_year() {
+        let old = "<!--- benchmarking table year=2023 --->";
+        let new = "<!--- benchmarking table year=2024 --->";
+        let mut s = format!("{old}\nkept\n{old}\n{new}");
+        update_content(&mut s, &get_mock_timings(), Some(2024), false).unwrap();
+
+        assert!(s.starts_with(&format!("{old}\nkept\n{old}\n{new}\n## Benchmarks 2024")));
+        assert_eq!(s.matches(new).count(), 2);
+    }
+
+    #[test]
+    fn shows_fingerprint() {
+        let mut timings = get_mock_timings();
+        timings.fingerprint = Some(Fingerprint {
+            cpu: "Apple M1".into(),
+            cores: 8,
+            rustc: "rustc 1.83.0 (90b35a623 2024-11-26)".into(),
+            target: "aarch64-apple-darwin".into(),
+            profile: "release".into(),
+            opt_level: "3".into(),
+            features: vec![],
+            rustflags: None,
+        });
+
+        let mut s = MARKER.to_string();
+        update_content(&mut s, &timings, None, false).unwrap();
+        assert!(s.ends_with(
+            "**Total: 190.00ms**\n\n<sub>Apple M1 (8 cores) · rustc 1.83.0 · aarch64-apple-darwin · release (opt-level 3)</sub>\n<!--- benchmarking table --->"
+        ));
+    }
+
+    #[test]
+    fn links_charts() {
+        let mut s = MARKER.to_string();
+        let charts = update_content(&mut s, &get_mock_timings(), None, false).unwrap();
+        assert!(charts.is_empty());
+        assert!(!s.contains("![Benchmark chart]"));
+
+        let charts = update_content(&mut s, &get_mock_timings(), None, true).unwrap();
+        assert_eq!(charts, vec!["./.assets/benchmarks.svg".to_string()]);
+        assert!(
+            s.contains("## Benchmarks\n\n![Benchmark chart](./.assets/benchmarks.svg)\n\n| Day")
+        );
+
+        // NOTE: once linked, the chart is kept up to date.
+        let charts = update_content(&mut s, &get_mock_timings(), None, false).unwrap();
+        assert_eq!(charts.len(), 1);
+        assert_eq!(s.matches("![Benchmark chart]").count(), 1);
+    }
+
+    #[test]
+    fn locates_tables_by_name() {
+        let s = "<!--- benchmarking tables --->\n<!--- benchmarking table unit=ms --->";
+        let tables = locate_tables(s, MARKER_NAME).unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].options, "unit=ms");
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        for options in [
+            "columns=day,speed",
+            "sort=size",
+            "unit=h",
+            "level=7",
+            "foo=bar",
+            "bar",
+        ] {
+            assert!(
+                options.parse::<TableOptions>().is_err(),
+                "expected `{options}` to be invalid"
+            );
+        }
+        assert_eq!("".parse::<TableOptions>().unwrap(), TableOptions::default());
+    }
 }
@@ -0,0 +1,104 @@
+/// Module that updates the readme with the stars collected so far.
+/// Renders the same table as `advent-readme-stars`, but from the puzzle descriptions in `data/puzzles`.
+use std::fs;
+
+use crate::template::readme_benchmarks::{locate_tables, Error};
+use crate::template::{all_days, puzzle, Day};
+
+static MARKER_NAME: &str = "advent_readme_stars table";
+
+fn construct_table(marker: &str, year: u16, stars: &[(Day, u8)]) -> String {
+    let mut lines: Vec<String> = vec![
+        marker.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for &(day, count) in stars.iter().filter(|(_, count)| *count > 0) {
+        let star = |part: u8| if count >= part { "⭐" } else { " " };
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} |",
+            day.into_inner(),
+            day.into_inner(),
+            star(1),
+            star(2)
+        ));
+    }
+
+    lines.push(marker.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: u16, stars: &[(Day, u8)]) -> Result<(), Error> {
+    // NOTE: replace back to front, so that earlier positions stay valid.
+    for position in locate_tables(s, MARKER_NAME)?.iter().rev() {
+        let table = construct_table(&position.marker, year, stars);
+        s.replace_range(position.pos_start..position.pos_end, &table);
+    }
+    Ok(())
+}
+
+/// Updates the stars table in the readme, returns the total number of stars.
+pub fn update(year: u16) -> Result<u32, Error> {
+    let path = "README.md";
+    let stars: Vec<(Day, u8)> = all_days()
+        .map(|day| (day, puzzle::read_stars(day)))
+        .collect();
+
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, &stars)?;
+    fs::write(path, &readme)?;
+
+    Ok(stars.iter().map(|(_, count)| u32::from(*count)).sum())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::update_content;
+    use crate::day;
+
+    static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+    #[test]
+    fn renders_collected_stars() {
+        let mut s = format!("# readme\n\n{MARKER}\n\n<!--- benchmarking table --->");
+        let stars = [(day!(1), 2), (day!(2), 1), (day!(3), 0)];
+        update_content(&mut s, 2024, &stars).unwrap();
+
+        let expected = [
+            "# readme",
+            "",
+            MARKER,
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |",
+            MARKER,
+            "",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn replaces_existing_table() {
+        let mut s = MARKER.to_string();
+        update_content(&mut s, 2024, &[(day!(1), 1)]).unwrap();
+        update_content(&mut s, 2024, &[(day!(1), 2)]).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## 2024 Results").count(), 1);
+        assert_eq!(s.matches('⭐').count(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, 2024, &[]).unwrap();
+    }
+}
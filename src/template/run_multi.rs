@@ -1,41 +1,38 @@
-use std::{collections::HashSet, io};
+use std::io;
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, DaySet, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
-use super::{
-    all_days,
-    timings::{Timing, Timings},
-};
+use super::timings::{Timing, Timings};
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(days_to_run: &DaySet, is_release: bool, is_timed: bool) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
-
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    for day in days_to_run.iter() {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+
+        if output.is_empty() {
+            println!("Not solved.");
+        } else {
+            let val = child_commands::parse_exec_time(&output, day);
+            timings.push(val);
+        }
+    }
 
     if is_timed {
-        let timings = Timings { data: timings };
+        let timings = Timings {
+            data: timings,
+            fingerprint: None,
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -68,7 +65,10 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        timings::{parse_duration, PartTiming, TimingStats},
+        Day,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,25 +76,68 @@ pub mod child_commands {
         thread,
     };
 
+    /// Features of this binary, which are passed on to every build of a solution bin so that
+    /// the solutions run, and are fingerprinted, with the same features.
+    pub fn features() -> Vec<&'static str> {
+        [
+            ("dhat-heap", cfg!(feature = "dhat-heap")),
+            ("today", cfg!(feature = "today")),
+        ]
+        .into_iter()
+        .filter_map(|(feature, enabled)| enabled.then_some(feature))
+        .collect()
+    }
+
+    /// Arguments of the `cargo` invocation that runs the solution bin of `day` with `features`.
+    fn cargo_args(
+        day: Day,
+        is_release: bool,
+        features: &[&str],
+        child_args: &[&str],
+    ) -> Vec<String> {
+        let mut args = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
+
+        if is_release {
+            args.push("--release".into());
+        }
+
+        if !features.is_empty() {
+            args.push("--features".into());
+            args.push(features.join(","));
+        }
+
+        if !child_args.is_empty() {
+            args.push("--".into());
+            args.extend(child_args.iter().map(ToString::to_string));
+        }
+
+        args
+    }
+
     /// Run the solution bin for a given day
     pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+        // mirror `--time` flag to child invocations.
+        let child_args: &[&str] = if is_timed { &["--time"] } else { &[] };
+        run_solution_with_args(day, is_release, child_args)
+    }
+
+    /// Run the solution bin for a given day, passing `child_args` to the solution.
+    pub fn run_solution_with_args(
+        day: Day,
+        is_release: bool,
+        child_args: &[&str],
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+        let args = cargo_args(day, is_release, &features(), child_args);
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
@@ -128,6 +171,26 @@ pub mod child_commands {
         Ok(output)
     }
 
+    /// Ask the solution bin for a given day to generate an input of roughly `size` bytes.
+    pub fn generate_input(day: Day, size: usize, seed: u64) -> Result<String, String> {
+        let (size, seed) = (size.to_string(), seed.to_string());
+        let output = Command::new("cargo")
+            .args(cargo_args(
+                day,
+                true,
+                &features(),
+                &["--generate", &size, "--seed", &seed],
+            ))
+            .output()
+            .map_err(|e| e.to_string())?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        }
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
@@ -139,54 +202,53 @@ pub mod child_commands {
         output
             .iter()
             .filter_map(|l| {
-                if !l.contains(" samples)") {
+                if !l.contains(" samples") {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some(timing) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing)| {
+                timings.total_nanos += timing.nanos;
+
                 if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
+                    timings.part_1 = Some(timing);
                 } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                    timings.part_2 = Some(timing);
                 }
-
-                timings.total_nanos += nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    /// Parses a timing such as `(74.1ns @ 10000 samples, 70.0ns..120.0ns)` at the end of a line.
+    fn parse_time(line: &str) -> Option<PartTiming> {
+        let (_, timing) = line.trim_end().strip_suffix(')')?.rsplit_once('(')?;
+        let (average, rest) = timing.split_once('@')?;
+        let (samples, stats) = match rest.split_once(',') {
+            Some((samples, stats)) => (samples, Some(stats)),
+            None => (rest, None),
+        };
+
+        let stats = stats.and_then(|stats| {
+            let (min, max) = stats.trim().split_once("..")?;
+            Some(TimingStats {
+                min_nanos: parse_duration(min)?,
+                max_nanos: parse_duration(max)?,
+            })
+        });
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        Some(PartTiming {
+            nanos: parse_duration(average.trim())?,
+            samples: Some(samples.trim().strip_suffix(" samples")?.parse().ok()?),
+            stats,
+        })
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,9 +267,31 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{cargo_args, parse_exec_time};
 
-        use crate::day;
+        use crate::{day, template::timings::TimingStats};
+
+        #[test]
+        fn passes_features_before_child_args() {
+            assert_eq!(
+                cargo_args(day!(3), true, &["dhat-heap", "today"], &["--time"]),
+                [
+                    "run",
+                    "--quiet",
+                    "--bin",
+                    "03",
+                    "--release",
+                    "--features",
+                    "dhat-heap,today",
+                    "--",
+                    "--time"
+                ]
+            );
+            assert_eq!(
+                cargo_args(day!(3), false, &[], &[]),
+                ["run", "--quiet", "--bin", "03"]
+            );
+        }
 
         #[test]
         fn parses_execution_times() {
@@ -220,8 +304,29 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            let (part_1, part_2) = (res.part_1.unwrap(), res.part_2.unwrap());
+            assert_approx_eq!(part_1.nanos, 74.13_f64);
+            assert_eq!(part_1.samples, Some(100000));
+            assert_approx_eq!(part_2.nanos, 74130000_f64);
+            assert_eq!(part_2.samples, Some(99999));
+        }
+
+        #[test]
+        fn parses_execution_time_stats() {
+            let res = parse_exec_time(
+                &["Part 1: 42 (74.1µs @ 10000 samples, 70.0µs..1.2ms)".into()],
+                day!(1),
+            );
+            let part_1 = res.part_1.unwrap();
+            assert_approx_eq!(part_1.nanos, 74100_f64);
+            assert_eq!(
+                part_1.stats,
+                Some(TimingStats {
+                    min_nanos: 70000_f64,
+                    max_nanos: 1200000_f64
+                })
+            );
+            assert_eq!(res.part_2, None);
         }
 
         #[test]
@@ -235,8 +340,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.part_1.unwrap().nanos, 2000000000_f64);
+            assert_approx_eq!(res.part_2.unwrap().nanos, 100000000_f64);
         }
 
         #[test]
@@ -12,24 +12,27 @@ use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, run) = run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &format_run(&run));
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Execution time of a solution part.
+struct Run {
+    average: Duration,
+    samples: u128,
+    min: Duration,
+    max: Duration,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+fn run_timed<I: Copy, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, Run) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -44,13 +47,18 @@ fn run_timed<I: Copy, T>(
     let run = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Run {
+            average: base_time,
+            samples: 1,
+            min: base_time,
+            max: base_time,
+        }
     };
 
-    (result, run.0, run.1)
+    (result, run)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Run {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -67,11 +75,13 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
+    Run {
         #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+        average: Duration::from_nanos(average_duration(&timers) as u64),
+        samples: bench_iterations,
+        min: timers.iter().min().copied().unwrap_or(*base_time),
+        max: timers.iter().max().copied().unwrap_or(*base_time),
+    }
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -82,11 +92,18 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_run(run: &Run) -> String {
+    let Run {
+        average,
+        samples,
+        min,
+        max,
+    } = run;
+
+    if *samples == 1 {
+        format!(" ({average:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({average:.1?} @ {samples} samples, {min:.1?}..{max:.1?})")
     }
 }
 
@@ -0,0 +1,81 @@
+//! Module that estimates how the run time of a solution grows with the size of its input.
+
+/// Input sizes in bytes, doubling `steps - 1` times from `start`.
+pub fn sizes(start: usize, steps: usize) -> Vec<usize> {
+    (0..steps).map(|step| start << step).collect()
+}
+
+/// Slope of the least-squares line through `(ln size, ln nanos)` of `samples`,
+/// i.e. the exponent `k` of a run time that grows like `size^k`.
+pub fn growth_exponent(samples: &[(f64, f64)]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|(size, nanos)| *size > 0.0 && *nanos > 0.0)
+        .map(|(size, nanos)| (size.ln(), nanos.ln()))
+        .collect();
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    // NOTE: the number of sizes is small.
+    #[allow(clippy::cast_precision_loss)]
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// Names the complexity class closest to `exponent`, if it is close enough to one.
+pub fn describe_exponent(exponent: f64) -> Option<&'static str> {
+    ["constant", "linear", "quadratic", "cubic"]
+        .into_iter()
+        .zip([0.0, 1.0, 2.0, 3.0])
+        .find(|(_, k)| (exponent - k).abs() < 0.25)
+        .map(|(name, _)| name)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{describe_exponent, growth_exponent, sizes};
+
+    #[test]
+    fn doubles_sizes() {
+        assert_eq!(sizes(1000, 4), vec![1000, 2000, 4000, 8000]);
+        assert!(sizes(1000, 0).is_empty());
+    }
+
+    #[test]
+    fn fits_growth_exponents() {
+        let linear: Vec<(f64, f64)> = sizes(100, 5)
+            .into_iter()
+            .map(|n| (n as f64, 3.0 * n as f64))
+            .collect();
+        assert!((growth_exponent(&linear).unwrap() - 1.0).abs() < 1e-9);
+
+        let quadratic: Vec<(f64, f64)> = sizes(100, 5)
+            .into_iter()
+            .map(|n| (n as f64, (n * n) as f64 + 50.0))
+            .collect();
+        assert!((growth_exponent(&quadratic).unwrap() - 2.0).abs() < 0.01);
+
+        assert_eq!(growth_exponent(&[(100.0, 5.0)]), None);
+        assert_eq!(growth_exponent(&[(100.0, 5.0), (100.0, 7.0)]), None);
+        assert_eq!(growth_exponent(&[(100.0, 5.0), (200.0, 0.0)]), None);
+    }
+
+    #[test]
+    fn describes_exponents() {
+        assert_eq!(describe_exponent(1.07), Some("linear"));
+        assert_eq!(describe_exponent(1.93), Some("quadratic"));
+        assert_eq!(describe_exponent(-0.1), Some("constant"));
+        assert_eq!(describe_exponent(1.5), None);
+    }
+}
@@ -0,0 +1,249 @@
+/// Module that resolves and renders the templates used by `scaffold` to create solution files.
+use std::{fmt::Display, fs, io, path::Path};
+
+use crate::template::{aoc_cli, Day};
+
+static USER_TEMPLATE_DIR: &str = "./data/templates";
+
+pub static DEFAULT_TEMPLATE: &str = "default";
+pub static DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// Templates that ship with the repository, as `(name, contents)` pairs.
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "default",
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt")),
+    ),
+    (
+        "lines",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/lines.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "blocks",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/blocks.txt"
+        )),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/graph.txt"
+        )),
+    ),
+];
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound(String, Vec<String>),
+    /// The template uses `%YEAR%`, but no year is configured.
+    MissingYear,
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotFound(name, available) => write!(
+                f,
+                "unknown template \"{name}\", available templates: {}.",
+                available.join(", ")
+            ),
+            Error::MissingYear => write!(
+                f,
+                "the template uses %YEAR%, but `AOC_YEAR` is not set in .cargo/config.toml or the environment."
+            ),
+            Error::IO(e) => write!(f, "could not read template: {e}"),
+        }
+    }
+}
+
+/// Values that are substituted into a template when rendering it.
+pub struct TemplateContext {
+    pub day: Day,
+    pub year: Option<u16>,
+    pub title: Option<String>,
+    pub answer_type: String,
+}
+
+impl TemplateContext {
+    /// Builds the context for a day, picking up the year from the environment and the title from
+    /// a previously downloaded puzzle description.
+    pub fn for_day(day: Day, answer_type: Option<&str>) -> Self {
+        Self {
+            day,
+            year: aoc_cli::get_year(),
+            title: read_puzzle_title(day),
+            answer_type: answer_type.unwrap_or(DEFAULT_ANSWER_TYPE).to_string(),
+        }
+    }
+}
+
+/// Returns the names of all templates that can be passed to `scaffold --template`.
+/// User templates shadow built-in templates with the same name.
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(USER_TEMPLATE_DIR) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                    if !names.iter().any(|n| n == name) {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    names
+}
+
+/// Loads a template by name, looking in the user template directory before the built-in templates.
+pub fn load(name: &str) -> Result<String, Error> {
+    let user_path = Path::new(USER_TEMPLATE_DIR).join(format!("{name}.txt"));
+    if user_path.is_file() {
+        return Ok(fs::read_to_string(user_path)?);
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, contents)| (*contents).to_string())
+        .ok_or_else(|| Error::NotFound(name.into(), available()))
+}
+
+/// Replaces all placeholders in `template` with the values in `context`.
+///
+/// Supported placeholders are `%DAY_NUMBER%` (e.g. `8`), `%DAY%` (e.g. `08`), `%YEAR%`,
+/// `%TITLE%` and `%ANSWER_TYPE%`. Fails if the template uses `%YEAR%` without a year.
+pub fn render(template: &str, context: &TemplateContext) -> Result<String, Error> {
+    let year = match context.year {
+        Some(year) => year.to_string(),
+        None if template.contains("%YEAR%") => return Err(Error::MissingYear),
+        None => String::new(),
+    };
+
+    let title = context
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Day {}", context.day.into_inner()));
+
+    Ok(template
+        .replace("%DAY_NUMBER%", &context.day.into_inner().to_string())
+        .replace("%DAY%", &context.day.to_string())
+        .replace("%YEAR%", &year)
+        .replace("%TITLE%", &title)
+        .replace("%ANSWER_TYPE%", &context.answer_type))
+}
+
+fn read_puzzle_title(day: Day) -> Option<String> {
+    let puzzle = fs::read_to_string(format!("data/puzzles/{day}.md")).ok()?;
+    parse_title(&puzzle)
+}
+
+/// Extracts the title from a puzzle description downloaded by aoc-cli,
+/// e.g. `\--- Day 1: Historian Hysteria ---` becomes `Day 1: Historian Hysteria`.
+fn parse_title(puzzle: &str) -> Option<String> {
+    puzzle
+        .lines()
+        .find(|line| line.contains("--- Day "))
+        .map(|line| {
+            line.trim_matches(|c: char| c == '#' || c == '\\' || c == '-' || c.is_whitespace())
+                .to_string()
+        })
+        .filter(|title| !title.is_empty())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, parse_title, render, Error, TemplateContext};
+    use crate::day;
+
+    fn context() -> TemplateContext {
+        TemplateContext {
+            day: day!(8),
+            year: Some(2024),
+            title: Some("Day 8: Resonant Collinearity".into()),
+            answer_type: "u64".into(),
+        }
+    }
+
+    #[test]
+    fn renders_all_placeholders() {
+        let rendered = render(
+            "%DAY_NUMBER% %DAY% %YEAR% %TITLE% %ANSWER_TYPE%",
+            &context(),
+        )
+        .unwrap();
+        assert_eq!(rendered, "8 08 2024 Day 8: Resonant Collinearity u64");
+    }
+
+    #[test]
+    fn falls_back_to_day_title() {
+        let mut context = context();
+        context.title = None;
+        assert_eq!(render("//! %TITLE%", &context).unwrap(), "//! Day 8");
+    }
+
+    #[test]
+    fn requires_a_year_for_year_placeholder() {
+        let mut context = context();
+        context.year = None;
+        assert!(matches!(
+            render("// %YEAR% day %DAY%", &context),
+            Err(Error::MissingYear)
+        ));
+        assert_eq!(render("// day %DAY%", &context).unwrap(), "// day 08");
+    }
+
+    #[test]
+    fn renders_builtin_templates() {
+        for name in ["default", "lines", "grid", "blocks", "graph"] {
+            let rendered = render(&load(name).unwrap(), &context()).unwrap();
+            assert!(rendered.contains("advent_of_code::solution!(8);"));
+            assert!(rendered.contains("Option<u64>"));
+            assert!(!rendered.contains('%'));
+        }
+    }
+
+    #[test]
+    fn errors_for_unknown_template() {
+        assert!(load("does-not-exist").is_err());
+    }
+
+    #[test]
+    fn parses_puzzle_titles() {
+        let puzzle = "\\--- Day 1: Historian Hysteria ---\n----------\n\nThe *Chief Historian* is always present";
+        assert_eq!(
+            parse_title(puzzle),
+            Some("Day 1: Historian Hysteria".to_string())
+        );
+        assert_eq!(
+            parse_title("## --- Day 12: Garden Groups ---"),
+            Some("Day 12: Garden Groups".to_string())
+        );
+        assert_eq!(parse_title("no title here"), None);
+    }
+}
@@ -1,16 +1,54 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{fingerprint::Fingerprint, Day};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Version of the JSON document written by [`Timings::store_file`].
+/// Documents without a version are the legacy format that stored pre-formatted strings per part.
+const SCHEMA_VERSION: u32 = 2;
+
+/// Spread of the samples of a benchmarked part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimingStats {
+    pub min_nanos: f64,
+    pub max_nanos: f64,
+}
+
+/// Represents the benchmark time of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Average execution time.
+    pub nanos: f64,
+    /// Number of benchmark iterations, unknown for migrated timings.
+    pub samples: Option<u64>,
+    pub stats: Option<TimingStats>,
+}
+
+impl PartTiming {
+    pub fn new(nanos: f64) -> Self {
+        Self {
+            nanos,
+            samples: None,
+            stats: None,
+        }
+    }
+}
+
+impl Display for PartTiming {
+    /// Formats the average execution time, e.g. `74.1ms`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format_duration(self.nanos))
+    }
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
 }
 
@@ -19,6 +57,8 @@ pub struct Timing {
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// Environment the timings were measured in, unknown for timings stored by older versions.
+    pub fingerprint: Option<Fingerprint>,
 }
 
 impl Timings {
@@ -38,6 +78,7 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// The merged timings take the fingerprint of `other`.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -51,8 +92,11 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|t| t.day);
+        Timings {
+            data,
+            fingerprint: new.fingerprint.clone().or_else(|| self.fingerprint.clone()),
+        }
     }
 
     /// Sum up total duration of timings as millis.
@@ -67,12 +111,41 @@ impl Timings {
     }
 }
 
+/// Parses a duration formatted by `Duration`'s `Debug` impl (e.g. `74.13ms`) to nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse_to_float = |postfix: &str| s.split(postfix).next()?.parse::<f64>().ok();
+
+    match s {
+        s if s.contains("ns") => parse_to_float("ns"),
+        s if s.contains("µs") => parse_to_float("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float("ms").map(|x| x * 1_000_000_f64),
+        _ => parse_to_float("s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// Formats nanoseconds the same way the runner prints durations, e.g. `74.1ms`.
+pub fn format_duration(nanos: f64) -> String {
+    // NOTE: timings are positive and far below the range of u64.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("version".into(), JsonValue::Number(SCHEMA_VERSION.into()));
+        map.insert(
+            "fingerprint".into(),
+            value
+                .fingerprint
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,19 +161,40 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // NOTE: legacy documents are migrated part by part, see `TryFrom<&JsonValue> for PartTiming`.
+        if let Some(version) = json.get("version") {
+            let version = version
+                .get::<f64>()
+                .ok_or("expected `json.version` to be a number.")?;
+            if *version > f64::from(SCHEMA_VERSION) {
+                return Err(format!(
+                    "unsupported timings version {version}, expected at most {SCHEMA_VERSION}."
+                ));
+            }
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        let fingerprint = json
+            .get("fingerprint")
+            .filter(|v| !v.is_null())
+            .map(Fingerprint::try_from)
+            .transpose()?;
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            fingerprint,
         })
     }
 }
@@ -114,24 +208,11 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        let part_1 = value.part_1.as_ref().map(JsonValue::from);
+        let part_2 = value.part_2.as_ref().map(JsonValue::from);
 
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
-
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        map.insert("part_1".into(), part_1.unwrap_or(JsonValue::Null));
+        map.insert("part_2".into(), part_2.unwrap_or(JsonValue::Null));
 
         JsonValue::Object(map)
     }
@@ -153,13 +234,17 @@ impl TryFrom<&JsonValue> for Timing {
 
         let part_1 = json
             .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+            .ok_or("Expected timing.part_1 to be present.")?;
+        let part_1 = (!part_1.is_null())
+            .then(|| PartTiming::try_from(part_1))
+            .transpose()?;
 
         let part_2 = json
             .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+            .ok_or("Expected timing.part_2 to be present.")?;
+        let part_2 = (!part_2.is_null())
+            .then(|| PartTiming::try_from(part_2))
+            .transpose()?;
 
         let total_nanos = json
             .get("total_nanos")
@@ -168,8 +253,8 @@ impl TryFrom<&JsonValue> for Timing {
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            part_1,
+            part_2,
             total_nanos,
         })
     }
@@ -177,50 +262,154 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+
+        // NOTE: sample counts are capped by the runner and well within the range that f64 represents exactly.
+        #[allow(clippy::cast_precision_loss)]
+        let samples = value
+            .samples
+            .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64));
+        map.insert("samples".into(), samples);
+
+        if let Some(stats) = value.stats {
+            let mut stats_map: HashMap<String, JsonValue> = HashMap::new();
+            stats_map.insert("min_nanos".into(), JsonValue::Number(stats.min_nanos));
+            stats_map.insert("max_nanos".into(), JsonValue::Number(stats.max_nanos));
+            map.insert("stats".into(), JsonValue::Object(stats_map));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        // legacy (version 1) timings stored pre-formatted strings, e.g. `"74.13ms"`.
+        if let Some(formatted) = value.get::<String>() {
+            return parse_duration(formatted)
+                .map(PartTiming::new)
+                .ok_or_else(|| format!("Could not parse legacy part timing `{formatted}`."));
+        }
+
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be null, a string or an object.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part.nanos to be a number.")?;
+
+        // NOTE: sample counts are whole, positive numbers.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64);
+
+        let stats = json
+            .get("stats")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .map(|stats| {
+                let get = |key: &str| stats.get(key).and_then(|v| v.get::<f64>().copied());
+                Some(TimingStats {
+                    min_nanos: get("min_nanos")?,
+                    max_nanos: get("max_nanos")?,
+                })
+            })
+            .map(|stats| stats.ok_or("Expected part.stats to contain min_nanos and max_nanos."))
+            .transpose()?;
+
+        Ok(PartTiming {
+            nanos,
+            samples,
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(PartTiming::new(10e6)),
+                    part_2: Some(PartTiming::new(20e6)),
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(PartTiming::new(30e6)),
+                    part_2: Some(PartTiming::new(40e6)),
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: Some(PartTiming::new(40e6)),
                     part_2: None,
                     total_nanos: 4e+10,
                 },
             ],
+            fingerprint: None,
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{PartTiming, TimingStats, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 100, "stats": { "min_nanos": 900000, "max_nanos": 1500000 } }, "part_2": { "nanos": 2000000, "samples": 10 }, "total_nanos": 3000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(
+                timing.part_1,
+                Some(PartTiming {
+                    nanos: 1_000_000_f64,
+                    samples: Some(100),
+                    stats: Some(TimingStats {
+                        min_nanos: 900_000_f64,
+                        max_nanos: 1_500_000_f64
+                    }),
+                })
+            );
+            assert_eq!(timing.part_2.as_ref().unwrap().stats, None);
+            assert_eq!(timing.total_nanos, 3_000_000_f64);
+        }
+
+        #[test]
+        fn migrates_legacy_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.13ms", "part_2": null, "total_nanos": 74130000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1, Some(PartTiming::new(74_130_000_f64)));
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_1.as_ref().unwrap().to_string(), "74.1ms");
+        }
+
+        #[test]
+        fn rejects_newer_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
         }
 
         #[test]
@@ -247,9 +436,28 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{PartTiming, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_1 = Some(PartTiming {
+                nanos: 74.13,
+                samples: Some(10_000),
+                stats: None,
+            });
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            assert!(json.contains(r#""version":2"#));
+
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -271,7 +479,7 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartTiming, Timing, Timings},
         };
 
         #[test]
@@ -279,13 +487,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(PartTiming::new(1e6)),
+                    part_2: Some(PartTiming::new(2e6)),
                     total_nanos: 3_000_000_000_f64,
                 }],
+                fingerprint: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +502,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: Some(PartTiming::new(1e6)),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
+                fingerprint: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -311,17 +521,20 @@ mod tests {
                     part_2: None,
                     total_nanos: 0.0,
                 }],
+                fingerprint: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::fingerprint::Fingerprint,
+            template::timings::{PartTiming, Timing, Timings},
         };
+        use tinyjson::JsonValue;
 
         use super::get_mock_timings;
 
@@ -335,6 +548,7 @@ mod tests {
                     part_2: None,
                     total_nanos: 0_f64,
                 }],
+                fingerprint: None,
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -355,6 +569,7 @@ mod tests {
                     part_2: None,
                     total_nanos: 0_f64,
                 }],
+                fingerprint: None,
             };
             let merged = timings.merge(&other);
 
@@ -380,5 +595,35 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_latest_fingerprint() {
+            let fingerprint = |cores| Fingerprint {
+                cpu: "Apple M1".into(),
+                cores,
+                rustc: "rustc 1.83.0".into(),
+                target: "aarch64-apple-darwin".into(),
+                profile: "release".into(),
+                opt_level: "3".into(),
+                features: vec![],
+                rustflags: None,
+            };
+
+            let mut timings = get_mock_timings();
+            timings.fingerprint = Some(fingerprint(8));
+
+            let merged = timings.merge(&Timings::default());
+            assert_eq!(merged.fingerprint, Some(fingerprint(8)));
+
+            let other = Timings {
+                data: vec![],
+                fingerprint: Some(fingerprint(4)),
+            };
+            let merged = timings.merge(&other);
+            assert_eq!(merged.fingerprint, Some(fingerprint(4)));
+
+            let parsed = Timings::try_from(JsonValue::from(merged).stringify().unwrap()).unwrap();
+            assert_eq!(parsed.fingerprint, Some(fingerprint(4)));
+        }
     }
 }
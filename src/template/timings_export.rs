@@ -0,0 +1,395 @@
+/// Module that converts timings and their history to formats for other tools, e.g. spreadsheets.
+use std::{fmt::Display, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{PartTiming, Timing, TimingStats, Timings};
+use crate::template::timings_history::{format_timestamp, HistoryEntry};
+use crate::template::Day;
+
+/// Columns of a timing in CSV exports. All durations are in nanoseconds.
+const CSV_COLUMNS: [&str; 10] = [
+    "day",
+    "part_1_nanos",
+    "part_1_samples",
+    "part_1_min_nanos",
+    "part_1_max_nanos",
+    "part_2_nanos",
+    "part_2_samples",
+    "part_2_min_nanos",
+    "part_2_max_nanos",
+    "total_nanos",
+];
+
+/// Columns that precede the timing columns in CSV exports of the history.
+const CSV_HISTORY_COLUMNS: [&str; 4] = ["timestamp", "commit", "dirty", "profile"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Markdown,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = FormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "md" | "markdown" => Ok(Format::Markdown),
+            "json" => Ok(Format::Json),
+            _ => Err(FormatFromStrError(s.into())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FormatFromStrError(String);
+
+impl std::error::Error for FormatFromStrError {}
+
+impl Display for FormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown export format `{}`, expecting csv, md or json",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn csv_cells(timing: &Timing) -> Vec<String> {
+    let mut cells = vec![timing.day.into_inner().to_string()];
+
+    for part in [&timing.part_1, &timing.part_2] {
+        let stats = part.as_ref().and_then(|p| p.stats);
+        cells.extend(
+            [
+                part.as_ref().map(|p| p.nanos.to_string()),
+                part.as_ref().and_then(|p| p.samples).map(|s| s.to_string()),
+                stats.map(|s| s.min_nanos.to_string()),
+                stats.map(|s| s.max_nanos.to_string()),
+            ]
+            .map(Option::unwrap_or_default),
+        );
+    }
+
+    cells.push(timing.total_nanos.to_string());
+    cells
+}
+
+/// Quotes a CSV field if needed.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.into()
+    }
+}
+
+/// Formats nanoseconds as microseconds, the unit of markdown exports.
+fn micros(nanos: f64) -> String {
+    format!("{:.1}", nanos / 1000_f64)
+}
+
+fn markdown_cells(timing: &Timing) -> Vec<String> {
+    let part = |part: &Option<PartTiming>| part.as_ref().map_or("-".into(), |p| micros(p.nanos));
+    vec![
+        timing.day.into_inner().to_string(),
+        part(&timing.part_1),
+        part(&timing.part_2),
+        micros(timing.total_nanos),
+    ]
+}
+
+fn markdown_table(header: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut lines = vec![
+        format!("| {} |", header.join(" | ")),
+        format!("|{}", " ---: |".repeat(header.len())),
+    ];
+    lines.extend(rows.iter().map(|row| format!("| {} |", row.join(" | "))));
+    lines.join("\n") + "\n"
+}
+
+/// Serializes the stored timings, ordered by day.
+pub fn export_timings(timings: &Timings, format: Format) -> String {
+    let mut data: Vec<&Timing> = timings.data.iter().collect();
+    data.sort_by_key(|t| t.day);
+
+    match format {
+        Format::Csv => {
+            let mut lines = vec![CSV_COLUMNS.join(",")];
+            lines.extend(data.iter().map(|t| csv_cells(t).join(",")));
+            lines.join("\n") + "\n"
+        }
+        Format::Markdown => markdown_table(
+            &["Day", "Part 1 (µs)", "Part 2 (µs)", "Total (µs)"],
+            data.iter().map(|t| markdown_cells(t)).collect(),
+        ),
+        Format::Json => {
+            let json = JsonValue::from(Timings {
+                data: data.into_iter().cloned().collect(),
+                fingerprint: timings.fingerprint.clone(),
+            });
+            json.format().unwrap_or_default() + "\n"
+        }
+    }
+}
+
+/// Serializes every timing of every stored run, oldest run first.
+pub fn export_history(history: &[HistoryEntry], format: Format) -> String {
+    let runs = || {
+        history.iter().flat_map(|entry| {
+            let mut data: Vec<&Timing> = entry.timings.data.iter().collect();
+            data.sort_by_key(|t| t.day);
+            data.into_iter().map(move |timing| (entry, timing))
+        })
+    };
+
+    match format {
+        Format::Csv => {
+            let mut lines = vec![format!(
+                "{},{}",
+                CSV_HISTORY_COLUMNS.join(","),
+                CSV_COLUMNS.join(",")
+            )];
+            lines.extend(runs().map(|(entry, timing)| {
+                let mut cells = vec![
+                    entry.timestamp.to_string(),
+                    csv_field(entry.commit.as_deref().unwrap_or_default()),
+                    entry.dirty.to_string(),
+                    csv_field(&entry.profile),
+                ];
+                cells.extend(csv_cells(timing));
+                cells.join(",")
+            }));
+            lines.join("\n") + "\n"
+        }
+        Format::Markdown => markdown_table(
+            &[
+                "Date (UTC)",
+                "Revision",
+                "Day",
+                "Part 1 (µs)",
+                "Part 2 (µs)",
+                "Total (µs)",
+            ],
+            runs()
+                .map(|(entry, timing)| {
+                    let mut cells = vec![format_timestamp(entry.timestamp), entry.revision()];
+                    cells.extend(markdown_cells(timing));
+                    cells
+                })
+                .collect(),
+        ),
+        Format::Json => {
+            let json = JsonValue::Array(history.iter().map(JsonValue::from).collect());
+            json.format().unwrap_or_default() + "\n"
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses timings from a CSV file in the format of [`export_timings`].
+pub fn import_csv(csv: &str) -> Result<Timings, String> {
+    let mut lines = csv
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let header = lines
+        .next()
+        .map(|(_, line)| line.trim())
+        .unwrap_or_default();
+    if header != CSV_COLUMNS.join(",") {
+        return Err(format!(
+            "expected the header `{}`, found `{header}`.",
+            CSV_COLUMNS.join(",")
+        ));
+    }
+
+    let data = lines
+        .map(|(i, line)| parse_csv_row(line).map_err(|e| format!("line {}: {e}", i + 1)))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Timings {
+        data,
+        fingerprint: None,
+    })
+}
+
+fn parse_csv_row(line: &str) -> Result<Timing, String> {
+    let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+    if cells.len() != CSV_COLUMNS.len() {
+        return Err(format!(
+            "expected {} columns, found {}.",
+            CSV_COLUMNS.len(),
+            cells.len()
+        ));
+    }
+
+    let number = |i: usize| -> Result<Option<f64>, String> {
+        if cells[i].is_empty() {
+            return Ok(None);
+        }
+        cells[i]
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("expected `{}` to be a number.", CSV_COLUMNS[i]))
+    };
+
+    let part = |offset: usize| -> Result<Option<PartTiming>, String> {
+        let Some(nanos) = number(offset)? else {
+            return Ok(None);
+        };
+
+        // NOTE: sample counts are whole, positive numbers.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = number(offset + 1)?.map(|s| s as u64);
+
+        let stats = match (number(offset + 2)?, number(offset + 3)?) {
+            (Some(min_nanos), Some(max_nanos)) => Some(TimingStats {
+                min_nanos,
+                max_nanos,
+            }),
+            _ => None,
+        };
+
+        Ok(Some(PartTiming {
+            nanos,
+            samples,
+            stats,
+        }))
+    };
+
+    Ok(Timing {
+        day: cells[0]
+            .parse::<Day>()
+            .map_err(|e| format!("invalid day: {e}"))?,
+        part_1: part(1)?,
+        part_2: part(5)?,
+        total_nanos: number(9)?.ok_or("expected `total_nanos` to be set.")?,
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{export_history, export_timings, import_csv, Format};
+    use crate::day;
+    use crate::template::timings::{PartTiming, Timing, TimingStats, Timings};
+    use crate::template::timings_history::HistoryEntry;
+    use crate::template::LAST_DAY;
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(2),
+                    part_1: Some(PartTiming::new(30e6)),
+                    part_2: None,
+                    total_nanos: 3e7,
+                },
+                Timing {
+                    day: day!(1),
+                    part_1: Some(PartTiming {
+                        nanos: 768.0,
+                        samples: Some(10000),
+                        stats: Some(TimingStats {
+                            min_nanos: 674.0,
+                            max_nanos: 57500.0,
+                        }),
+                    }),
+                    part_2: Some(PartTiming::new(1200.5)),
+                    total_nanos: 1968.5,
+                },
+            ],
+            fingerprint: None,
+        }
+    }
+
+    #[test]
+    fn exports_csv() {
+        assert_eq!(
+            export_timings(&get_mock_timings(), Format::Csv),
+            [
+                "day,part_1_nanos,part_1_samples,part_1_min_nanos,part_1_max_nanos,part_2_nanos,part_2_samples,part_2_min_nanos,part_2_max_nanos,total_nanos",
+                "1,768,10000,674,57500,1200.5,,,,1968.5",
+                "2,30000000,,,,,,,,30000000",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn exports_markdown() {
+        assert_eq!(
+            export_timings(&get_mock_timings(), Format::Markdown),
+            [
+                "| Day | Part 1 (µs) | Part 2 (µs) | Total (µs) |",
+                "| ---: | ---: | ---: | ---: |",
+                "| 1 | 0.8 | 1.2 | 2.0 |",
+                "| 2 | 30000.0 | - | 30000.0 |",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn exports_json_ordered_by_day() {
+        let json = export_timings(&get_mock_timings(), Format::Json);
+        let parsed = Timings::try_from(json).unwrap();
+        assert_eq!(parsed.data[0].day, day!(1));
+        assert_eq!(parsed.data[1].day, day!(2));
+    }
+
+    #[test]
+    fn roundtrips_csv() {
+        let timings = get_mock_timings();
+        let imported = import_csv(&export_timings(&timings, Format::Csv)).unwrap();
+
+        assert_eq!(imported.data.len(), 2);
+        assert_eq!(imported.data[0].part_1, timings.data[1].part_1);
+        assert_eq!(imported.data[0].part_2, timings.data[1].part_2);
+        assert_eq!(imported.data[0].total_nanos, timings.data[1].total_nanos);
+        assert_eq!(imported.data[1].part_1, timings.data[0].part_1);
+        assert_eq!(imported.data[1].part_2, None);
+    }
+
+    #[test]
+    fn rejects_malformed_csv() {
+        assert!(import_csv("day,nanos\n1,2").is_err());
+
+        let header = export_timings(&Timings::default(), Format::Csv);
+        assert!(import_csv(&format!("{header}1,2,3")).is_err());
+        assert!(import_csv(&format!("{header}{},1,,,,,,,,1", LAST_DAY + 1)).is_err());
+        assert!(import_csv(&format!("{header}1,x,,,,,,,,1")).is_err());
+        assert!(import_csv(&format!("{header}1,1,,,,,,,,")).is_err());
+        assert!(import_csv(&header).unwrap().data.is_empty());
+    }
+
+    #[test]
+    fn exports_history_csv() {
+        let history = vec![HistoryEntry {
+            timestamp: 1_733_650_000,
+            commit: Some("1c99156".into()),
+            dirty: true,
+            profile: "release".into(),
+            timings: get_mock_timings(),
+        }];
+
+        let csv = export_history(&history, Format::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("timestamp,commit,dirty,profile,day,"));
+        assert!(lines[1].starts_with("1733650000,1c99156,true,release,1,768,"));
+
+        let md = export_history(&history, Format::Markdown);
+        assert!(md.contains("| 2024-12-08 09:26 | 1c99156+ | 2 | 30000.0 | - | 30000.0 |"));
+    }
+}
@@ -0,0 +1,325 @@
+/// Module that keeps an append-only log of stored benchmark runs.
+/// Every line of the history file is a JSON document describing one run.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::fingerprint::Fingerprint;
+use crate::template::timings::{PartTiming, Timing, Timings};
+use crate::template::Day;
+
+static HISTORY_FILE_PATH: &str = "./data/timings-history.jsonl";
+
+/// A single stored benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Abbreviated hash of the checked out commit, if the project is a git repository.
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    /// Cargo profile the solutions were built with.
+    pub profile: String,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Creates an entry for `timings` that were just measured in the current working tree.
+    pub fn new(timings: Timings, profile: &str) -> Self {
+        let (commit, dirty) = git_revision();
+
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            commit,
+            dirty,
+            profile: profile.into(),
+            timings,
+        }
+    }
+
+    /// Short description of the revision, e.g. `1c99156+` for a dirty tree.
+    pub fn revision(&self) -> String {
+        let commit = self.commit.as_deref().unwrap_or("unknown");
+        if self.dirty {
+            format!("{commit}+")
+        } else {
+            commit.into()
+        }
+    }
+}
+
+/// Appends a run to the history file.
+pub fn append(entry: &HistoryEntry) -> Result<(), io::Error> {
+    let line = JsonValue::from(entry)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all stored runs, oldest first. If the history file is not present, returns no runs.
+pub fn read() -> Result<Vec<HistoryEntry>, String> {
+    let Ok(contents) = fs::read_to_string(HISTORY_FILE_PATH) else {
+        return Ok(vec![]);
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            HistoryEntry::try_from(line).map_err(|e| format!("{HISTORY_FILE_PATH}:{}: {e}", i + 1))
+        })
+        .collect()
+}
+
+/// Queries git for the current commit and whether the working tree is dirty.
+fn git_revision() -> (Option<String>, bool) {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let commit = git(&["rev-parse", "--short", "HEAD"]);
+    let dirty = commit.is_some() && git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty());
+
+    (commit, dirty)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A measurement of a single part in a run.
+#[derive(Debug)]
+pub struct PartSample<'a> {
+    pub entry: &'a HistoryEntry,
+    pub timing: &'a PartTiming,
+}
+
+/// Collects the measurements of `part` for `day`, oldest first.
+pub fn samples(history: &[HistoryEntry], day: Day, part: u8) -> Vec<PartSample<'_>> {
+    history
+        .iter()
+        .filter_map(|entry| {
+            let timing = entry.timings.data.iter().find(|t| t.day == day)?;
+            let timing = match part {
+                1 => timing.part_1.as_ref(),
+                _ => timing.part_2.as_ref(),
+            }?;
+
+            Some(PartSample { entry, timing })
+        })
+        .collect()
+}
+
+/// Formats a unix timestamp as a UTC date, e.g. `2024-12-08 10:00`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+
+    // NOTE: converts days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        // NOTE: timestamps are well within the range that f64 represents exactly.
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("dirty".into(), JsonValue::Boolean(value.dirty));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+        map.insert(
+            "fingerprint".into(),
+            value
+                .timings
+                .fingerprint
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not a valid JSON line."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected history entry to be an object.")?;
+
+        // NOTE: timestamps are stored as whole seconds.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("expected `timestamp` to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("expected `commit` to be null or string.")?
+            .cloned();
+
+        let dirty = json
+            .get("dirty")
+            .and_then(|v| v.get::<bool>().copied())
+            .ok_or("expected `dirty` to be a boolean.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>().cloned())
+            .ok_or("expected `profile` to be a string.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected `data` to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        let fingerprint = json
+            .get("fingerprint")
+            .filter(|v| !v.is_null())
+            .map(Fingerprint::try_from)
+            .transpose()?;
+
+        Ok(Self {
+            timestamp,
+            commit,
+            dirty,
+            profile,
+            timings: Timings { data, fingerprint },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, samples, HistoryEntry};
+    use crate::day;
+    use crate::template::fingerprint::Fingerprint;
+    use crate::template::timings::{PartTiming, Timing, Timings};
+    use tinyjson::JsonValue;
+
+    fn entry(timestamp: u64, commit: &str, part_1: f64) -> HistoryEntry {
+        HistoryEntry {
+            timestamp,
+            commit: Some(commit.into()),
+            dirty: false,
+            profile: "release".into(),
+            timings: Timings {
+                data: vec![Timing {
+                    day: day!(11),
+                    part_1: Some(PartTiming::new(part_1)),
+                    part_2: None,
+                    total_nanos: 0.0,
+                }],
+                fingerprint: None,
+            },
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let mut original = entry(1_733_650_000, "1c99156", 74_130_000.0);
+        original.dirty = true;
+
+        let line = JsonValue::from(&original).stringify().unwrap();
+        assert!(!line.contains('\n'));
+
+        let parsed = HistoryEntry::try_from(line.as_str()).unwrap();
+        assert_eq!(parsed.timestamp, 1_733_650_000);
+        assert_eq!(parsed.revision(), "1c99156+");
+        assert_eq!(parsed.profile, "release");
+        assert_eq!(
+            parsed.timings.data[0].part_1,
+            Some(PartTiming::new(74_130_000.0))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        assert!(HistoryEntry::try_from("{}").is_err());
+        assert!(HistoryEntry::try_from("not json").is_err());
+    }
+
+    #[test]
+    fn collects_part_samples() {
+        let history = vec![
+            entry(1, "a", 3_500_000.0),
+            entry(2, "b", 900_000.0),
+            entry(3, "c", 1_200_000.0),
+        ];
+
+        let nanos: Vec<f64> = samples(&history, day!(11), 1)
+            .iter()
+            .map(|s| s.timing.nanos)
+            .collect();
+        assert_eq!(nanos, vec![3_500_000.0, 900_000.0, 1_200_000.0]);
+        assert!(samples(&history, day!(11), 2).is_empty());
+        assert!(samples(&history, day!(1), 1).is_empty());
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_650_000), "2024-12-08 09:26");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+}
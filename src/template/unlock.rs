@@ -0,0 +1,148 @@
+/// Module that computes when puzzles unlock on the Advent of Code server.
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use crate::template::{aoc_cli, default_event_length, Day, LAST_DAY};
+
+/// Puzzles unlock at midnight in the server timezone (EST, which has no daylight saving time in december).
+const SERVER_UTC_OFFSET: i32 = -5;
+
+fn server_offset() -> FixedOffset {
+    // NOTE: the offset is a constant that is well within the valid range.
+    FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap()
+}
+
+/// Converts a point in time to the server timezone.
+pub fn server_time(time: DateTime<Utc>) -> DateTime<FixedOffset> {
+    time.with_timezone(&server_offset())
+}
+
+/// Number of puzzles of the event in `year`, respecting `AOC_DAYS` for the configured year.
+pub fn event_length(year: i32) -> u8 {
+    let year = u16::try_from(year).ok();
+    if year.is_some() && year == aoc_cli::get_year() {
+        LAST_DAY
+    } else {
+        default_event_length(year)
+    }
+}
+
+/// Returns the point in time at which the puzzle for `day` of `year` unlocks.
+pub fn unlock_time(year: i32, day: Day) -> DateTime<Utc> {
+    server_offset()
+        .with_ymd_and_hms(year, 12, u32::from(day.into_inner()), 0, 0, 0)
+        .single()
+        .expect("december days are valid dates.")
+        .with_timezone(&Utc)
+}
+
+/// A puzzle that has not been unlocked yet.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Unlock {
+    pub year: i32,
+    pub day: Day,
+    pub time: DateTime<Utc>,
+}
+
+/// Returns the next puzzle that unlocks after `now`.
+pub fn next_unlock(now: DateTime<Utc>) -> Unlock {
+    let today = server_time(now);
+    let year = today.year();
+
+    let upcoming = (1..=event_length(year))
+        .filter_map(Day::new)
+        .map(|day| Unlock {
+            year,
+            day,
+            time: unlock_time(year, day),
+        })
+        .find(|unlock| unlock.time > now);
+
+    upcoming.unwrap_or_else(|| {
+        // NOTE: every event starts on the 1st of december.
+        let day = Day::new(1).unwrap();
+        Unlock {
+            year: year + 1,
+            day,
+            time: unlock_time(year + 1, day),
+        }
+    })
+}
+
+/// Formats the time between `now` and `until` as `1d 02:03:04`.
+pub fn format_countdown(now: DateTime<Utc>, until: DateTime<Utc>) -> String {
+    let seconds = (until - now).num_seconds().max(0);
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds % 86_400 / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+    );
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_countdown, next_unlock, unlock_time};
+    use crate::day;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        assert_eq!(
+            unlock_time(2024, day!(5)),
+            Utc.with_ymd_and_hms(2024, 12, 5, 5, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn finds_next_unlock_during_event() {
+        let now = Utc.with_ymd_and_hms(2024, 12, 5, 4, 59, 59).unwrap();
+        let unlock = next_unlock(now);
+        assert_eq!((unlock.year, unlock.day), (2024, day!(5)));
+
+        let now = Utc.with_ymd_and_hms(2024, 12, 5, 5, 0, 0).unwrap();
+        let unlock = next_unlock(now);
+        assert_eq!((unlock.year, unlock.day), (2024, day!(6)));
+    }
+
+    #[test]
+    fn finds_next_unlock_outside_of_event() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 19, 12, 0, 0).unwrap();
+        let unlock = next_unlock(now);
+        assert_eq!((unlock.year, unlock.day), (2026, day!(1)));
+        assert_eq!(
+            unlock.time,
+            Utc.with_ymd_and_hms(2026, 12, 1, 5, 0, 0).unwrap()
+        );
+
+        let now = Utc.with_ymd_and_hms(2023, 12, 30, 12, 0, 0).unwrap();
+        let unlock = next_unlock(now);
+        assert_eq!((unlock.year, unlock.day), (2024, day!(1)));
+    }
+
+    #[test]
+    fn respects_shorter_events() {
+        let now = Utc.with_ymd_and_hms(2025, 12, 12, 6, 0, 0).unwrap();
+        let unlock = next_unlock(now);
+        assert_eq!((unlock.year, unlock.day), (2026, day!(1)));
+    }
+
+    #[test]
+    fn formats_countdown() {
+        let now = Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            format_countdown(now, Utc.with_ymd_and_hms(2024, 12, 1, 5, 0, 9).unwrap()),
+            "05:00:09"
+        );
+        assert_eq!(
+            format_countdown(now, Utc.with_ymd_and_hms(2024, 12, 3, 1, 2, 3).unwrap()),
+            "2d 01:02:03"
+        );
+        assert_eq!(format_countdown(now, now), "00:00:00");
+    }
+}
@@ -0,0 +1,226 @@
+//! Cycle detection for simulations that eventually repeat a state.
+//!
+//! A sequence `x0, x1, x2, ...` with `x[i + 1] = next(x[i])` either ends or enters a cycle:
+//! after `start` steps (μ) it repeats with `period` (λ). States only need `Clone + Eq`,
+//! and the detectors use constant memory, unlike collecting seen states in a `HashSet`.
+//!
+//! ```ignore
+//! let cycle = brent(state, |s| Some(step(s))).unwrap();
+//! let state = fast_forward(state, step, 1_000_000_000);
+//! ```
+
+/// Where a sequence starts repeating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Index of the first state that is part of the cycle (μ).
+    pub start: usize,
+    /// Length of the cycle (λ).
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The smallest index with the same state as index `n`.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Floyd's tortoise and hare over the states produced by `next`, starting at `initial`.
+/// Returns `None` if `next` returns `None` before a state repeats.
+pub fn floyd<S: Clone + Eq>(initial: S, next: impl Fn(&S) -> Option<S>) -> Option<Cycle> {
+    floyd_iter(std::iter::successors(Some(initial), |s| next(s)))
+}
+
+/// Brent's algorithm over the states produced by `next`, starting at `initial`.
+/// Usually needs fewer calls of `next` than [`floyd`].
+pub fn brent<S: Clone + Eq>(initial: S, next: impl Fn(&S) -> Option<S>) -> Option<Cycle> {
+    brent_iter(std::iter::successors(Some(initial), |s| next(s)))
+}
+
+/// Floyd's tortoise and hare over the items of `iter`, which is cloned to restart the sequence.
+/// Returns `None` if the iterator ends before an item repeats.
+pub fn floyd_iter<I>(iter: I) -> Option<Cycle>
+where
+    I: Iterator + Clone,
+    I::Item: Eq,
+{
+    // the hare moves two items per step, so after `k` steps it is `k` items ahead
+    let mut tortoise = iter.clone();
+    let mut hare = iter.clone();
+    let mut k = 0;
+    loop {
+        k += 1;
+        let slow = tortoise.next()?;
+        hare.next()?;
+        if slow == hare.next()? {
+            break;
+        }
+    }
+
+    let (start, item, rest) = find_start(iter, k)?;
+    Some(Cycle {
+        start,
+        period: period_of(rest, &item)?,
+    })
+}
+
+/// Brent's algorithm over the items of `iter`, which is cloned to restart the sequence.
+/// Returns `None` if the iterator ends before an item repeats.
+pub fn brent_iter<I>(iter: I) -> Option<Cycle>
+where
+    I: Iterator + Clone,
+    I::Item: Clone + Eq,
+{
+    // the tortoise teleports to the hare at every power of two, until the hare catches it
+    let mut hare = iter.clone();
+    let mut tortoise = hare.next()?;
+    let mut power = 1;
+    let mut period = 1;
+    loop {
+        let item = hare.next()?;
+        if item == tortoise {
+            break;
+        }
+        if power == period {
+            tortoise = item;
+            power *= 2;
+            period = 0;
+        }
+        period += 1;
+    }
+
+    let (start, _, _) = find_start(iter, period)?;
+    Some(Cycle { start, period })
+}
+
+/// The first index `i` with `x[i] == x[i + distance]`, given that `distance` is a multiple
+/// of the period. Also returns that item and the iterator that follows it.
+fn find_start<I>(iter: I, distance: usize) -> Option<(usize, I::Item, I)>
+where
+    I: Iterator + Clone,
+    I::Item: Eq,
+{
+    let mut tortoise = iter.clone();
+    let mut hare = iter;
+    hare.nth(distance - 1)?;
+
+    let mut start = 0;
+    loop {
+        let item = tortoise.next()?;
+        if item == hare.next()? {
+            return Some((start, item, tortoise));
+        }
+        start += 1;
+    }
+}
+
+/// Number of items until `item` appears again in `rest`.
+fn period_of<I>(mut rest: I, item: &I::Item) -> Option<usize>
+where
+    I: Iterator,
+    I::Item: Eq,
+{
+    rest.position(|next| next == *item).map(|i| i + 1)
+}
+
+/// The state after `n` steps of `next` from `initial`,
+/// skipping whole cycles once the states repeat.
+pub fn fast_forward<S: Clone + Eq>(initial: S, next: impl Fn(&S) -> S, n: usize) -> S {
+    let mut states = std::iter::successors(Some(initial), |s| Some(next(s)));
+    let n = match brent_iter(states.clone().take(n.saturating_add(1))) {
+        Some(cycle) => cycle.index(n),
+        None => n,
+    };
+    states.nth(n).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(start: usize, period: usize) -> Option<Cycle> {
+        Some(Cycle { start, period })
+    }
+
+    // 0, 1, ..., 9, then 3..=9 over and over
+    fn rho(x: &u32) -> Option<u32> {
+        Some(if *x == 9 { 3 } else { x + 1 })
+    }
+
+    #[test]
+    fn test_detects_cycle() {
+        let expected = cycle(3, 7);
+        assert_eq!(floyd(0, rho), expected);
+        assert_eq!(brent(0, rho), expected);
+        assert_eq!(floyd(5, rho), cycle(0, 7));
+        assert_eq!(brent(5, rho), cycle(0, 7));
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        let expected = cycle(4, 1);
+        assert_eq!(floyd(0, |&x: &u32| Some((x + 1).min(4))), expected);
+        assert_eq!(brent(0, |&x: &u32| Some((x + 1).min(4))), expected);
+    }
+
+    #[test]
+    fn test_no_cycle() {
+        assert_eq!(floyd(0, |&x| (x < 10).then_some(x + 1)), None);
+        assert_eq!(brent(0, |&x| (x < 10).then_some(x + 1)), None);
+        assert_eq!(brent_iter(0..100), None);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let digits = [1, 2].into_iter().chain([3, 4, 5].into_iter().cycle());
+        let expected = cycle(2, 3);
+        assert_eq!(floyd_iter(digits.clone()), expected);
+        assert_eq!(brent_iter(digits), expected);
+    }
+
+    #[test]
+    fn test_agrees_with_naive() {
+        // x -> x² + 1 mod m has a rho shape for every start
+        for m in 2..60u64 {
+            for x in 0..m {
+                let next = |x: &u64| Some((x * x + 1) % m);
+                let mut seen = vec![];
+                let mut state = x;
+                while !seen.contains(&state) {
+                    seen.push(state);
+                    state = next(&state).unwrap();
+                }
+                let start = seen.iter().position(|&s| s == state).unwrap();
+                let expected = cycle(start, seen.len() - start);
+                assert_eq!(floyd(x, next), expected, "m = {m}, x = {x}");
+                assert_eq!(brent(x, next), expected, "m = {m}, x = {x}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let step = |x: &u32| rho(x).unwrap();
+        assert_eq!(fast_forward(0, step, 0), 0);
+        assert_eq!(fast_forward(0, step, 2), 2);
+        assert_eq!(fast_forward(0, step, 10), 3);
+        assert_eq!(
+            fast_forward(0, step, 1_000_000_000_000),
+            (3 + (1_000_000_000_000usize - 3) % 7) as u32
+        );
+        // never repeats within `n` steps
+        assert_eq!(fast_forward(0u64, |x| x + 1, 1000), 1000);
+        assert_eq!(
+            Cycle {
+                start: 3,
+                period: 7
+            }
+            .index(17),
+            3
+        );
+    }
+}
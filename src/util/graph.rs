@@ -0,0 +1,615 @@
+//! Graph searches over implicit graphs.
+//!
+//! Graphs are described by a `successors` function that returns the neighbours of a node,
+//! paired with the cost of the edge for the weighted searches. Nodes can be anything that is
+//! `Clone + Eq + Hash`, e.g. grid positions:
+//!
+//! ```ignore
+//! let paths = bfs([start], |&pos| grid.neighbours(pos).filter(|&next| grid[next] != '#'));
+//! let steps = paths.distance(&goal);
+//! ```
+//!
+//! Searches that take `starts` begin from all of them at once (multi-source).
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Costs of weighted edges, with `Default` as zero.
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+
+impl<C: Copy + Ord + Default + Add<Output = C>> Cost for C {}
+
+/// Distances of all nodes reached by a search, with the parents to reconstruct shortest paths.
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    distances: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Paths<N, C> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            parents: HashMap::new(),
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// A shortest path from one of the starts to `node`, including both.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.contains_key(node).then(|| {
+            let mut path = vec![node.clone()];
+            while let Some(parent) = self.parents.get(path.last().unwrap()) {
+                path.push(parent.clone());
+            }
+            path.reverse();
+            path
+        })
+    }
+
+    /// All reached nodes with their distances, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.distances.iter().map(|(node, &cost)| (node, cost))
+    }
+
+    /// Number of reached nodes.
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Breadth-first search from `starts` until `success` holds or every reachable node is visited.
+/// Returns the distances in steps, and the first node that matched `success`.
+fn bfs_until<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+) -> (Paths<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !paths.distances.contains_key(&start) {
+            paths.distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if success(&node) {
+            return (paths, Some(node));
+        }
+
+        let distance = paths.distances[&node] + 1;
+        for next in successors(&node) {
+            if !paths.distances.contains_key(&next) {
+                paths.distances.insert(next.clone(), distance);
+                paths.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    (paths, None)
+}
+
+/// Breadth-first search that visits every node reachable from `starts`.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_until(starts, successors, |_| false).0
+}
+
+/// A path with the fewest steps from one of `starts` to a node that matches `success`.
+pub fn bfs_path<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    success: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (paths, goal) = bfs_until(starts, successors, success);
+    paths.path_to(&goal?)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Nodes reachable from `start` in depth-first preorder,
+/// visiting successors in the order they are returned.
+pub fn dfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::new();
+    let mut order = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        let mut next: Vec<N> = successors(&node)
+            .into_iter()
+            .filter(|next| !visited.contains(next))
+            .collect();
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+
+    order
+}
+
+/// A path from `start` to a node that matches `success`, found depth-first.
+/// The path is not necessarily the shortest one.
+pub fn dfs_path<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut path = vec![start.clone()];
+    let mut visited = HashSet::from([start.clone()]);
+    let mut pending: Vec<std::vec::IntoIter<N>> = vec![];
+
+    if success(&start) {
+        return Some(path);
+    }
+    pending.push(
+        successors(&start)
+            .into_iter()
+            .collect::<Vec<_>>()
+            .into_iter(),
+    );
+
+    while let Some(children) = pending.last_mut() {
+        match children.find(|next| !visited.contains(next)) {
+            Some(next) => {
+                if success(&next) {
+                    path.push(next);
+                    return Some(path);
+                }
+                visited.insert(next.clone());
+                pending.push(
+                    successors(&next)
+                        .into_iter()
+                        .collect::<Vec<_>>()
+                        .into_iter(),
+                );
+                path.push(next);
+            }
+            None => {
+                pending.pop();
+                path.pop();
+            }
+        }
+    }
+
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Best-first search shared by Dijkstra and A*, ordered by cost plus `heuristic`.
+fn best_first<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut success: impl FnMut(&N) -> bool,
+) -> (Paths<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new();
+    let mut settled = HashSet::new();
+    // NOTE: nodes are stored in a separate list, so that they do not need to be `Ord`.
+    let mut nodes = vec![];
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        if !paths.distances.contains_key(&start) {
+            paths.distances.insert(start.clone(), C::default());
+            queue.push(Reverse((heuristic(&start), C::default(), nodes.len())));
+            nodes.push(start);
+        }
+    }
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let node = nodes[index].clone();
+        if paths.distances[&node] < cost || !settled.insert(node.clone()) {
+            continue;
+        }
+        if success(&node) {
+            return (paths, Some(node));
+        }
+
+        for (next, edge) in successors(&node) {
+            let next_cost = cost + edge;
+            let improves = paths
+                .distances
+                .get(&next)
+                .is_none_or(|&known| next_cost < known);
+            if improves {
+                paths.distances.insert(next.clone(), next_cost);
+                paths.parents.insert(next.clone(), node.clone());
+                queue.push(Reverse((
+                    next_cost + heuristic(&next),
+                    next_cost,
+                    nodes.len(),
+                )));
+                nodes.push(next);
+            }
+        }
+    }
+
+    (paths, None)
+}
+
+/// Dijkstra's algorithm from `starts` over every reachable node.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, successors, |_| C::default(), |_| false).0
+}
+
+/// A cheapest path from one of `starts` to a node that matches `success`, with its cost.
+pub fn dijkstra_path<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), success)
+}
+
+/// A* search for a cheapest path from one of `starts` to a node that matches `success`.
+/// `heuristic` must never overestimate the remaining cost, e.g. the Manhattan distance on a grid.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (paths, goal) = best_first(starts, successors, heuristic, success);
+    let goal = goal?;
+    Some((paths.path_to(&goal)?, paths.distances[&goal]))
+}
+
+/// Every cheapest path from one of `starts` to the nodes that match `success`, with their cost.
+pub fn all_shortest_paths<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<Vec<N>>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut distances: HashMap<N, C> = HashMap::new();
+    let mut parents: HashMap<N, Vec<N>> = HashMap::new();
+    let mut settled = HashSet::new();
+    let mut nodes = vec![];
+    let mut queue = BinaryHeap::new();
+    let mut goals = vec![];
+    let mut best = None;
+
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), C::default());
+            queue.push(Reverse((C::default(), nodes.len())));
+            nodes.push(start);
+        }
+    }
+
+    while let Some(Reverse((cost, index))) = queue.pop() {
+        if best.is_some_and(|best| cost > best) {
+            break;
+        }
+        let node = nodes[index].clone();
+        if distances[&node] < cost || !settled.insert(node.clone()) {
+            continue;
+        }
+        if success(&node) {
+            best = Some(cost);
+            goals.push(node);
+            continue;
+        }
+
+        for (next, edge) in successors(&node) {
+            let next_cost = cost + edge;
+            match distances.get(&next) {
+                Some(&known) if next_cost > known => {}
+                Some(&known) if next_cost == known => {
+                    parents.entry(next).or_default().push(node.clone());
+                }
+                _ => {
+                    distances.insert(next.clone(), next_cost);
+                    parents.insert(next.clone(), vec![node.clone()]);
+                    queue.push(Reverse((next_cost, nodes.len())));
+                    nodes.push(next);
+                }
+            }
+        }
+    }
+
+    // walk the parents back from every goal, extending partial paths that end at a start.
+    let mut paths = vec![];
+    let mut stack: Vec<Vec<N>> = goals.into_iter().map(|goal| vec![goal]).collect();
+    while let Some(path) = stack.pop() {
+        match parents.get(path.last().unwrap()) {
+            Some(parents) => stack.extend(parents.iter().map(|parent| {
+                let mut path = path.clone();
+                path.push(parent.clone());
+                path
+            })),
+            None => paths.push(path.into_iter().rev().collect()),
+        }
+    }
+
+    Some((paths, best?))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Number of distinct paths from `start` to nodes that match `success` in a directed acyclic graph.
+/// Paths end at the first node that matches `success`.
+///
+/// # Panics
+/// Panics if a cycle is reachable from `start`.
+pub fn count_paths<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut success: impl FnMut(&N) -> bool,
+) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut counts: HashMap<N, usize> = HashMap::new();
+    let mut in_progress = HashSet::new();
+    let mut stack = vec![(start.clone(), false)];
+
+    while let Some((node, expanded)) = stack.pop() {
+        if counts.contains_key(&node) {
+            continue;
+        }
+        if success(&node) {
+            counts.insert(node, 1);
+            continue;
+        }
+
+        if expanded {
+            let count = successors(&node)
+                .into_iter()
+                .map(|next| counts[&next])
+                .sum();
+            in_progress.remove(&node);
+            counts.insert(node, count);
+        } else {
+            in_progress.insert(node.clone());
+            stack.push((node.clone(), true));
+            for next in successors(&node) {
+                assert!(
+                    !in_progress.contains(&next),
+                    "count_paths requires an acyclic graph"
+                );
+                if !counts.contains_key(&next) {
+                    stack.push((next, false));
+                }
+            }
+        }
+    }
+
+    counts[&start]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::grid::{Grid, Pos};
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn maze() -> (Grid<char>, Pos, Pos) {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.position(&'S').unwrap();
+        let end = grid.position(&'E').unwrap();
+        (grid, start, end)
+    }
+
+    fn open(grid: &Grid<char>, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        grid.neighbours(pos).filter(|&next| grid[next] != '#')
+    }
+
+    // a small weighted graph with two cheapest routes from `a` to `e`
+    fn edges(node: &&'static str) -> Vec<(&'static str, u32)> {
+        match *node {
+            "a" => vec![("b", 1), ("c", 2), ("d", 10)],
+            "b" => vec![("e", 3)],
+            "c" => vec![("e", 2)],
+            "d" => vec![("e", 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let (grid, start, end) = maze();
+        let paths = bfs([start], |&pos| open(&grid, pos));
+        assert_eq!(paths.distance(&end), Some(15));
+        assert_eq!(paths.distance(&(0, 3)), None);
+        assert_eq!(paths.len(), grid.iter().filter(|(_, c)| **c != '#').count());
+
+        let path = paths.path_to(&end).unwrap();
+        assert_eq!(
+            (path.first(), path.last(), path.len()),
+            (Some(&start), Some(&end), 16)
+        );
+        assert!(path
+            .windows(2)
+            .all(|w| grid.neighbours(w[0]).any(|n| n == w[1])));
+
+        let path = bfs_path([start], |&pos| open(&grid, pos), |&pos| pos == end).unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!(
+            bfs_path([start], |&pos| open(&grid, pos), |&pos| pos == (0, 3)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_multi_source() {
+        let (grid, start, end) = maze();
+        let paths = bfs([start, end], |&pos| open(&grid, pos));
+        assert_eq!(paths.distance(&end), Some(0));
+        assert_eq!(paths.distance(&(0, 7)), Some(4));
+        assert_eq!(paths.path_to(&(0, 7)).unwrap().first(), Some(&end));
+    }
+
+    #[test]
+    fn test_dfs() {
+        assert_eq!(dfs("a", edges_unweighted), vec!["a", "b", "e", "c", "d"]);
+
+        let (grid, start, end) = maze();
+        let path = dfs_path(
+            start,
+            |&pos| open(&grid, pos).collect::<Vec<_>>(),
+            |&pos| pos == end,
+        )
+        .unwrap();
+        assert_eq!((path[0], *path.last().unwrap()), (start, end));
+        assert!(path
+            .windows(2)
+            .all(|w| grid.neighbours(w[0]).any(|n| n == w[1])));
+        assert_eq!(
+            dfs_path(
+                start,
+                |&pos| open(&grid, pos).collect::<Vec<_>>(),
+                |_| false
+            ),
+            None
+        );
+        assert_eq!(
+            dfs_path("e", edges_unweighted, |n| *n == "e"),
+            Some(vec!["e"])
+        );
+    }
+
+    fn edges_unweighted(node: &&'static str) -> Vec<&'static str> {
+        edges(node).into_iter().map(|(next, _)| next).collect()
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let paths = dijkstra(["a"], edges);
+        assert_eq!(paths.distance(&"e"), Some(4));
+        assert_eq!(paths.distance(&"d"), Some(10));
+
+        let (path, cost) = dijkstra_path(["a"], edges, |n| *n == "d").unwrap();
+        assert_eq!((path, cost), (vec!["a", "d"], 10));
+        assert_eq!(dijkstra_path(["b"], edges, |n| *n == "a"), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        let (grid, start, end) = maze();
+        let manhattan = |&(row, col): &Pos| end.0.abs_diff(row) + end.1.abs_diff(col);
+        let weighted = |&pos: &Pos| open(&grid, pos).map(|next| (next, 1));
+
+        let (path, cost) = astar([start], weighted, manhattan, |&pos| pos == end).unwrap();
+        assert_eq!((path.len(), cost), (16, 15));
+        assert_eq!(
+            dijkstra_path([start], weighted, |&pos| pos == end)
+                .unwrap()
+                .1,
+            15
+        );
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        let (mut paths, cost) = all_shortest_paths(["a"], edges, |n| *n == "e").unwrap();
+        paths.sort();
+        assert_eq!(cost, 4);
+        assert_eq!(paths, vec![vec!["a", "b", "e"], vec!["a", "c", "e"]]);
+
+        // every monotone route through an open 3x3 grid
+        let grid = Grid::new(3, 3, '.');
+        let (paths, cost) = all_shortest_paths(
+            [(0, 0)],
+            |&pos| grid.neighbours(pos).map(|next| (next, 1)),
+            |&pos| pos == (2, 2),
+        )
+        .unwrap();
+        assert_eq!((paths.len(), cost), (6, 4));
+
+        assert_eq!(all_shortest_paths(["b"], edges, |n| *n == "a"), None);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        assert_eq!(count_paths("a", edges_unweighted, |n| *n == "e"), 3);
+
+        let grid = Grid::new(4, 3, '.');
+        let down_right = |&(row, col): &Pos| {
+            grid.neighbours((row, col))
+                .filter(move |&next| next > (row, col))
+        };
+        assert_eq!(count_paths((0, 0), down_right, |&pos| pos == (2, 3)), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "acyclic")]
+    fn test_count_paths_cycle() {
+        count_paths(0, |&n| [(n + 1) % 3], |&n| n == 5);
+    }
+}
@@ -0,0 +1,451 @@
+//! A dense two-dimensional grid backed by a flat `Vec`.
+//!
+//! Cells are addressed by a [`Pos`] of `(row, col)`, with `(0, 0)` in the top left corner.
+//! Moving by an [`Offset`] is bounds-checked, so walking off the grid yields `None` instead
+//! of requiring sentinel values around the puzzle input.
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// Position of a cell as `(row, col)`.
+pub type Pos = (usize, usize);
+
+/// Step between two positions as `(rows, cols)`.
+pub type Offset = (isize, isize);
+
+/// Offsets of the four orthogonal neighbours: up, right, down, left.
+pub const ORTHOGONAL: [Offset; 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets of the four diagonal neighbours: up-right, down-right, down-left, up-left.
+pub const DIAGONAL: [Offset; 4] = [(-1, 1), (1, 1), (1, -1), (-1, -1)];
+
+/// Offsets of all eight neighbours, clockwise starting with up.
+pub const ADJACENT: [Offset; 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A line has a different length than the first one.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character could not be converted into a cell.
+    InvalidCell { pos: Pos, value: char },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(f, "line {} has {found} cells, expected {expected}", row + 1),
+            ParseGridError::InvalidCell { pos, value } => write!(
+                f,
+                "invalid cell `{value}` at line {}, column {}",
+                pos.0 + 1,
+                pos.1 + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl<T> Grid<T> {
+    /// Creates a grid of `width` x `height` cells that all hold `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    /// Panics if the number of cells is not a multiple of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "{} cells do not fill rows of width {width}",
+            cells.len()
+        );
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses one cell per character, with one row per line.
+    /// Returns an error if `parse` rejects a character or if lines differ in length.
+    pub fn parse_with(
+        input: &str,
+        mut parse: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseGridError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            let start = cells.len();
+            for (col, value) in line.chars().enumerate() {
+                let cell = parse(value).ok_or(ParseGridError::InvalidCell {
+                    pos: (row, col),
+                    value,
+                })?;
+                cells.push(cell);
+            }
+
+            let found = cells.len() - start;
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(ParseGridError::Ragged {
+                    row,
+                    expected,
+                    found,
+                });
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    /// Index of `pos` in the row-major cells, e.g. to key a dense lookup table.
+    pub fn to_index(&self, (row, col): Pos) -> usize {
+        row * self.width + col
+    }
+
+    /// Position of the cell at `index` in row-major order.
+    pub fn to_pos(&self, index: usize) -> Pos {
+        (index / self.width, index % self.width)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.to_index(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let index = self.to_index(pos);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Moves from `pos` by `offset`, returns `None` if the result lies outside the grid.
+    pub fn offset(&self, (row, col): Pos, (d_row, d_col): Offset) -> Option<Pos> {
+        let pos = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Positions of the orthogonal neighbours of `pos` inside the grid.
+    pub fn neighbours(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// Positions of the orthogonal and diagonal neighbours of `pos` inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ADJACENT
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// Positions from `pos` (inclusive) in steps of `offset` until leaving the grid.
+    pub fn ray(&self, pos: Pos, offset: Offset) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.contains(pos).then_some(pos), move |&pos| {
+            self.offset(pos, offset)
+        })
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |index| (index / width, index % width))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Position of the first cell in row-major order that matches `predicate`.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| self.to_pos(index))
+    }
+
+    /// Position of the first cell in row-major order that equals `value`.
+    pub fn position(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.find(|cell| cell == value)
+    }
+
+    /// Cells of a row, left to right.
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // NOTE: `chunks` panics on a chunk size of zero.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Cells of a column, top to bottom.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} is out of bounds");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Diagonals running down-right, starting with the one at the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height)
+            .rev()
+            .map(|row| (row, 0))
+            .chain((1..self.width).map(|col| (0, col)));
+        starts.map(|start| self.ray(start, (1, 1)).map(|pos| &self[pos]))
+    }
+
+    /// Diagonals running down-left, starting with the one at the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let last_col = self.width.saturating_sub(1);
+        let starts = (0..self.width)
+            .map(|col| (0, col))
+            .chain((1..self.height).map(move |row| (row, last_col)));
+        starts.map(|start| self.ray(start, (1, -1)).map(|pos| &self[pos]))
+    }
+
+    /// Creates a grid of the same size by applying `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, Some)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the grid"))
+    }
+}
+
+/// Renders one line per row, without separators between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    fn collect<'a>(cells: impl Iterator<Item = &'a char>) -> String {
+        cells.collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 2, 6));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.to_string(), INPUT);
+
+        let digits = Grid::parse_with("12\n34", |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits, Grid::from_vec(2, vec![1, 2, 3, 4]));
+
+        let empty: Grid<char> = "".parse().unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.rows().count(), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(ParseGridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        let err = Grid::parse_with("12\n3x", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(err.to_string(), "invalid cell `x` at line 2, column 2");
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!(grid.get((1, 2)), Some(&'f'));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+        assert_eq!(grid.offset((1, 2), (0, 1)), None);
+
+        *grid.get_mut((0, 1)).unwrap() = 'x';
+        grid[(1, 1)] = 'y';
+        assert_eq!(grid.to_string(), "axc\ndyf\n");
+        assert!(grid.get_mut((5, 5)).is_none());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbours((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 2), (2, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours8((2, 2)).collect::<Vec<_>>(),
+            vec![(1, 2), (2, 1), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!(grid.position(&'e'), Some((1, 1)));
+        assert_eq!(grid.position(&'z'), None);
+        assert_eq!(grid.find(|c| *c > 'c'), Some((1, 0)));
+        assert_eq!(grid.to_pos(grid.to_index((1, 2))), (1, 2));
+        assert_eq!(
+            grid.iter()
+                .filter(|(_, c)| "aei".contains(**c))
+                .map(|(pos, _)| pos)
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(collect(grid.column(2)), "cfi");
+        assert_eq!(
+            grid.columns().map(collect).collect::<Vec<_>>(),
+            vec!["adg", "beh", "cfi"]
+        );
+        assert_eq!(
+            grid.diagonals().map(collect).collect::<Vec<_>>(),
+            vec!["g", "dh", "aei", "bf", "c"]
+        );
+        assert_eq!(
+            grid.anti_diagonals().map(collect).collect::<Vec<_>>(),
+            vec!["a", "bd", "ceg", "fh", "i"]
+        );
+        assert_eq!(
+            grid.ray((0, 2), (1, -1))
+                .map(|pos| grid[pos])
+                .collect::<String>(),
+            "ceg"
+        );
+        assert_eq!(grid.ray((3, 0), (0, 1)).count(), 0);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = Grid::parse_with("12\n34", |c| c.to_digit(10)).unwrap();
+        assert_eq!(grid.map(|n| n * 2).to_string(), "24\n68\n");
+    }
+}
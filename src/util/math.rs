@@ -0,0 +1,397 @@
+//! Number theory and digit arithmetic on integers.
+//!
+//! Everything is generic over [`Integer`], which is implemented for all primitive integers.
+//! The functions that need negative intermediate values ([`egcd`], [`modinv`] and [`crt`])
+//! take signed integers only. Functions that can overflow panic, and have a `checked_` variant
+//! that returns `None` instead.
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Primitive integers.
+pub trait Integer:
+    Copy
+    + Debug
+    + Default
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TEN: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const TEN: Self = 10;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn checked_pow(self, exp: u32) -> Option<Self> {
+                <$t>::checked_pow(self, exp)
+            }
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                <$t>::rem_euclid(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+fn abs<T: Integer>(n: T) -> T {
+    if n < T::ZERO {
+        T::ZERO - n
+    } else {
+        n
+    }
+}
+
+/// Greatest common divisor, which is never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    abs(a)
+}
+
+/// Least common multiple, which is never negative. It is 0 if either number is 0.
+///
+/// # Panics
+/// Panics if the result overflows.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).unwrap_or_else(|| panic!("lcm({a:?}, {b:?}) overflows"))
+}
+
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    abs(a / gcd(a, b)).checked_mul(abs(b))
+}
+
+/// Extended Euclid: returns `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+pub fn egcd<T: Integer + Neg<Output = T>>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::ONE, T::ZERO);
+    let (mut y0, mut y1) = (T::ZERO, T::ONE);
+    while r1 != T::ZERO {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < T::ZERO {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn modinv<T: Integer + Neg<Output = T>>(a: T, m: T) -> Option<T> {
+    let m = abs(m);
+    let (g, x, _) = egcd(a.rem_euclid(m), m);
+    (g == T::ONE).then(|| x.rem_euclid(m))
+}
+
+/// `a + b` modulo `m`, for `a` and `b` in `0..m`, without overflowing.
+fn add_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `a * b` modulo `m`, for `a` and `b` in `0..m`, without overflowing.
+fn mul_mod<T: Integer>(mut a: T, mut b: T, m: T) -> T {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let two = T::ONE + T::ONE;
+    let mut result = T::ZERO;
+    while b != T::ZERO {
+        if b % two == T::ONE {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b / two;
+    }
+    result
+}
+
+/// Chinese Remainder Theorem: the smallest non-negative `x` with `x ≡ residue (mod modulus)`
+/// for all pairs of `(residue, modulus)`, together with the lcm of the moduli.
+/// The moduli do not need to be coprime.
+///
+/// Returns `None` if the congruences contradict each other, or if the lcm of the moduli
+/// does not fit in `T`.
+///
+/// # Panics
+/// Panics if a modulus is not positive.
+pub fn crt<T: Integer + Neg<Output = T>>(
+    congruences: impl IntoIterator<Item = (T, T)>,
+) -> Option<(T, T)> {
+    let (mut x, mut m) = (T::ZERO, T::ONE);
+    for (residue, modulus) in congruences {
+        assert!(
+            modulus > T::ZERO,
+            "moduli must be positive, got {modulus:?}"
+        );
+        let residue = residue.rem_euclid(modulus);
+
+        // x + m * t ≡ residue (mod modulus) has a solution iff g divides the difference
+        let (g, p, _) = egcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != T::ZERO {
+            return None;
+        }
+        let n = modulus / g;
+        let t = mul_mod((diff / g).rem_euclid(n), p.rem_euclid(n), n);
+
+        let combined = m.checked_mul(n)?;
+        x = x + m * t;
+        m = combined;
+    }
+    Some((x, m))
+}
+
+/// Floor of the square root.
+///
+/// # Panics
+/// Panics if `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    assert!(n >= T::ZERO, "square root of negative number {n:?}");
+    let two = T::ONE + T::ONE;
+    if n < two {
+        return n;
+    }
+    // Newton's method decreases monotonically from any start above the root
+    let mut x = n / two + T::ONE;
+    let mut y = (x + n / x) / two;
+    while y < x {
+        x = y;
+        y = (x + n / x) / two;
+    }
+    x
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Number of decimal digits, ignoring the sign. 0 has one digit.
+pub fn digit_count<T: Integer>(mut n: T) -> u32 {
+    let mut count = 1;
+    while n / T::TEN != T::ZERO {
+        n = n / T::TEN;
+        count += 1;
+    }
+    count
+}
+
+/// Decimal digits of `abs(n)`, most significant first.
+pub fn digits<T: Integer>(n: T) -> Vec<T> {
+    let mut n = abs(n);
+    let mut digits = vec![];
+    loop {
+        digits.push(n % T::TEN);
+        n = n / T::TEN;
+        if n == T::ZERO {
+            break;
+        }
+    }
+    digits.reverse();
+    digits
+}
+
+/// `10^exp`.
+///
+/// # Panics
+/// Panics if the result overflows.
+pub fn pow10<T: Integer>(exp: u32) -> T {
+    checked_pow10(exp).unwrap_or_else(|| panic!("10^{exp} overflows"))
+}
+
+pub fn checked_pow10<T: Integer>(exp: u32) -> Option<T> {
+    T::TEN.checked_pow(exp)
+}
+
+/// Splits off the last `low` digits: `split_digits(123456, 2) == (1234, 56)`.
+/// Both parts keep the sign of `n`.
+pub fn split_digits<T: Integer>(n: T, low: u32) -> (T, T) {
+    match checked_pow10::<T>(low) {
+        Some(divisor) => (n / divisor, n % divisor),
+        None => (T::ZERO, n),
+    }
+}
+
+/// Appends the digits of `b` to `a`: `concat(12, 345) == 12345`.
+///
+/// # Panics
+/// Panics if the result overflows, or if `b` is negative.
+pub fn concat<T: Integer>(a: T, b: T) -> T {
+    checked_concat(a, b).unwrap_or_else(|| panic!("concatenating {a:?} and {b:?} overflows"))
+}
+
+/// Returns `None` if the result overflows, or if `b` is negative.
+pub fn checked_concat<T: Integer>(a: T, b: T) -> Option<T> {
+    if b < T::ZERO {
+        return None;
+    }
+    let shifted = a.checked_mul(checked_pow10(digit_count(b))?)?;
+    if a < T::ZERO {
+        shifted.checked_add(T::ZERO - b)
+    } else {
+        shifted.checked_add(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::prop;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0u8, 7), 7);
+        assert_eq!(gcd(0i32, 0), 0);
+        assert_eq!(lcm(4usize, 6), 12);
+        assert_eq!(lcm(-4i32, 6), 12);
+        assert_eq!(lcm(101, 103), 10403);
+        assert_eq!(lcm(0u32, 5), 0);
+        assert_eq!(checked_lcm(200u8, 3), None);
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows")]
+    fn test_lcm_overflow() {
+        lcm(250u8, 7);
+    }
+
+    #[test]
+    fn test_egcd() {
+        prop::check(
+            &prop::zip(prop::int(-1000..=1000i64), prop::int(-1000..=1000i64)),
+            |&(a, b)| {
+                let (g, x, y) = egcd(a, b);
+                g == gcd(a, b) && a * x + b * y == g
+            },
+        );
+    }
+
+    #[test]
+    fn test_modinv() {
+        assert_eq!(modinv(3i32, 11), Some(4));
+        assert_eq!(modinv(-3i32, 11), Some(7));
+        assert_eq!(modinv(6i64, 9), None);
+        assert_eq!(modinv(101i64, 103), Some(51));
+        prop::check(&prop::int(1..=1000i64), |&a| {
+            modinv(a, 1009).is_some_and(|inv| a * inv % 1009 == 1)
+        });
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // non-coprime moduli
+        assert_eq!(crt([(2i64, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1i32, 101), (-1, 103)]), Some((10402, 10403)));
+        assert_eq!(crt(Vec::<(i32, i32)>::new()), Some((0, 1)));
+        // the product of the residues overflows, but the solution fits
+        let big = (1i64 << 61) - 1;
+        assert_eq!(crt([(big - 1, big), (0, 2)]), Some((big - 1, 2 * big)));
+        assert_eq!(crt([(0i8, 100), (0, 3)]), None);
+    }
+
+    #[test]
+    fn test_crt_agrees_with_search() {
+        let congruence = prop::zip(prop::int(-50..=50i64), prop::int(1..=12i64));
+        prop::check(&prop::vec(congruence, 1..=3), |congruences| {
+            let modulus = congruences.iter().fold(1, |m, &(_, n)| lcm(m, n));
+            let expected = (0..modulus)
+                .find(|x| congruences.iter().all(|&(r, n)| (x - r).rem_euclid(n) == 0))
+                .map(|x| (x, modulus));
+            crt(congruences.iter().copied()) == expected
+        });
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0u32), 0);
+        assert_eq!(isqrt(1i32), 1);
+        assert_eq!(isqrt(15u64), 3);
+        assert_eq!(isqrt(16u64), 4);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt(i128::MAX), 13043817825332782212);
+        prop::check(&prop::int(0..=1_000_000_000_000u64), |&n| {
+            let root = isqrt(n);
+            root * root <= n && (root + 1) * (root + 1) > n
+        });
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(digit_count(0u8), 1);
+        assert_eq!(digit_count(9u8), 1);
+        assert_eq!(digit_count(255u8), 3);
+        assert_eq!(digit_count(-1000i32), 4);
+        assert_eq!(digit_count(u64::MAX), 20);
+        assert_eq!(digits(4096u32), vec![4, 0, 9, 6]);
+        assert_eq!(digits(-12i32), vec![1, 2]);
+        assert_eq!(digits(0usize), vec![0]);
+        assert_eq!(pow10::<u64>(19), 10_000_000_000_000_000_000);
+        assert_eq!(checked_pow10::<u64>(20), None);
+    }
+
+    #[test]
+    fn test_split_concat() {
+        assert_eq!(split_digits(123456u32, 2), (1234, 56));
+        assert_eq!(split_digits(1000usize, 2), (10, 0));
+        assert_eq!(split_digits(-1234i32, 2), (-12, -34));
+        assert_eq!(split_digits(200u8, 3), (0, 200));
+        assert_eq!(concat(12u32, 345), 12345);
+        assert_eq!(concat(10u32, 0), 100);
+        assert_eq!(concat(-12i32, 34), -1234);
+        assert_eq!(checked_concat(26u8, 5), None);
+        assert_eq!(checked_concat(1i32, -1), None);
+
+        prop::check(&prop::int(0..=1_000_000_000_000u64), |&n| {
+            let text = n.to_string();
+            digit_count(n) as usize == text.len()
+                && (1..digit_count(n)).all(|low| {
+                    let (high, rest) = text.split_at(text.len() - low as usize);
+                    split_digits(n, low) == (high.parse().unwrap(), rest.parse().unwrap())
+                })
+        });
+
+        let pair = prop::zip(prop::int(1..=1_000_000u64), prop::int(0..=1_000_000u64));
+        prop::check(&pair, |&(a, b)| {
+            concat(a, b).to_string() == format!("{a}{b}")
+        });
+    }
+}
@@ -1 +1,9 @@
+pub mod cycle;
+pub mod graph;
+pub mod grid;
+pub mod math;
+pub mod order;
+pub mod parse;
+pub mod point;
+pub mod prop;
 pub mod union_find;
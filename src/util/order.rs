@@ -0,0 +1,335 @@
+//! Topological sorting and ordering rules.
+//!
+//! [`topological_sort`] orders any subset of nodes of a directed graph given by a
+//! `successors` function, like the searches in [`graph`](super::graph). [`Rules`] holds
+//! pairwise `before|after` constraints, checks sequences against them and reorders them.
+//!
+//! ```ignore
+//! let rules: Rules<u32> = [(47, 53), (97, 13)].into_iter().collect();
+//! if let Err(violation) = rules.validate(&update) {
+//!     update = rules.sort(&update)?;
+//! }
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+
+/// A cycle that prevents a topological order, listed in edge order with the first node repeated
+/// at the end, e.g. `[a, b, c, a]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+    pub cycle: Vec<N>,
+}
+
+impl<N: Display> Display for CycleError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle in ordering: ")?;
+        for (i, node) in self.cycle.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{node}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<N: Debug + Display> std::error::Error for CycleError<N> {}
+
+/// Kahn's algorithm over `nodes`, where `successors` returns the nodes that must come after
+/// a node. Successors that are not in `nodes` are ignored, so this also sorts a subset of a
+/// larger graph.
+///
+/// Among the nodes that could come next, the one that appears first in `nodes` is taken,
+/// so a sequence that is already ordered is returned unchanged. Repeated nodes are kept.
+pub fn topological_sort<N, I>(
+    nodes: &[N],
+    mut successors: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, CycleError<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut index: HashMap<&N, Vec<usize>> = HashMap::new();
+    for (i, node) in nodes.iter().enumerate() {
+        index.entry(node).or_default().push(i);
+    }
+
+    // edges between the given nodes only, by position
+    let mut edges = vec![vec![]; nodes.len()];
+    let mut in_degree = vec![0; nodes.len()];
+    for (i, node) in nodes.iter().enumerate() {
+        for next in successors(node) {
+            for &j in index.get(&next).into_iter().flatten() {
+                edges[i].push(j);
+                in_degree[j] += 1;
+            }
+        }
+    }
+
+    let mut ready: BinaryHeap<_> = (0..nodes.len())
+        .filter(|&i| in_degree[i] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(nodes.len());
+    while let Some(Reverse(i)) = ready.pop() {
+        order.push(nodes[i].clone());
+        for &j in &edges[i] {
+            in_degree[j] -= 1;
+            if in_degree[j] == 0 {
+                ready.push(Reverse(j));
+            }
+        }
+    }
+
+    if order.len() < nodes.len() {
+        return Err(CycleError {
+            cycle: find_cycle(&edges, &in_degree)
+                .into_iter()
+                .map(|i| nodes[i].clone())
+                .collect(),
+        });
+    }
+    Ok(order)
+}
+
+/// A cycle among the nodes that Kahn's algorithm left with a positive in-degree.
+/// Each of them has a predecessor that was left too, so walking predecessors must repeat a node.
+fn find_cycle(edges: &[Vec<usize>], in_degree: &[usize]) -> Vec<usize> {
+    let left = |i: usize| in_degree[i] > 0;
+    let mut predecessor = vec![None; edges.len()];
+    for (i, next) in edges.iter().enumerate().filter(|&(i, _)| left(i)) {
+        for &j in next.iter().filter(|&&j| left(j)) {
+            predecessor[j] = Some(i);
+        }
+    }
+
+    let mut seen = HashMap::new();
+    let mut walk = vec![];
+    let mut node = (0..edges.len()).find(|&i| left(i)).unwrap();
+    while !seen.contains_key(&node) {
+        seen.insert(node, walk.len());
+        walk.push(node);
+        node = predecessor[node].unwrap();
+    }
+
+    // the walk goes against the edges, so the cycle is reversed
+    let mut cycle = walk.split_off(seen[&node]);
+    cycle.push(node);
+    cycle.reverse();
+    cycle
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A pair of nodes in the wrong order: `before` must come before `after`, but does not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation<N> {
+    pub before: N,
+    pub after: N,
+}
+
+impl<N: Display> Display for Violation<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} must come before {}", self.before, self.after)
+    }
+}
+
+impl<N: Debug + Display> std::error::Error for Violation<N> {}
+
+/// Pairwise ordering rules: `(a, b)` means that `a` must come before `b` when both are present.
+#[derive(Debug, Clone)]
+pub struct Rules<N> {
+    after: HashMap<N, HashSet<N>>,
+}
+
+impl<N: Clone + Eq + Hash> Default for Rules<N> {
+    fn default() -> Self {
+        Self {
+            after: HashMap::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Rules<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires `before` to come before `after`.
+    pub fn add(&mut self, before: N, after: N) {
+        self.after.entry(before).or_default().insert(after);
+    }
+
+    /// Whether a rule requires `before` to come before `after`.
+    pub fn requires(&self, before: &N, after: &N) -> bool {
+        self.after
+            .get(before)
+            .is_some_and(|successors| successors.contains(after))
+    }
+
+    /// Checks every pair of `sequence`, returning the first pair in the wrong order.
+    pub fn validate(&self, sequence: &[N]) -> Result<(), Violation<N>> {
+        for (i, earlier) in sequence.iter().enumerate() {
+            if let Some(later) = sequence[i + 1..]
+                .iter()
+                .find(|later| self.requires(later, earlier))
+            {
+                return Err(Violation {
+                    before: later.clone(),
+                    after: earlier.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_ordered(&self, sequence: &[N]) -> bool {
+        self.validate(sequence).is_ok()
+    }
+
+    /// `sequence` in an order that satisfies the rules, changing as little as possible:
+    /// nodes that no rule relates keep their relative order where they can.
+    pub fn sort(&self, sequence: &[N]) -> Result<Vec<N>, CycleError<N>> {
+        topological_sort(sequence, |node| {
+            self.after.get(node).into_iter().flatten().cloned()
+        })
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for Rules<N> {
+    fn from_iter<T: IntoIterator<Item = (N, N)>>(iter: T) -> Self {
+        let mut rules = Rules::new();
+        for (before, after) in iter {
+            rules.add(before, after);
+        }
+        rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::prop;
+
+    const EXAMPLE: &str = "47|53 97|13 97|61 97|47 75|29 61|13 75|53 29|13 97|29 53|29 61|53 \
+                           97|53 61|29 47|13 75|47 97|75 47|61 75|61 47|29 75|13 53|13";
+
+    fn example() -> Rules<u32> {
+        EXAMPLE
+            .split_whitespace()
+            .map(|rule| {
+                let (before, after) = rule.split_once('|').unwrap();
+                (before.parse().unwrap(), after.parse().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        let rules = example();
+        assert_eq!(rules.validate(&[75, 47, 61, 53, 29]), Ok(()));
+        assert_eq!(rules.validate(&[]), Ok(()));
+        assert_eq!(
+            rules.validate(&[75, 97, 47, 61, 53]),
+            Err(Violation {
+                before: 97,
+                after: 75
+            })
+        );
+        // not adjacent
+        assert_eq!(
+            rules.validate(&[61, 29, 13, 53]),
+            Err(Violation {
+                before: 53,
+                after: 29
+            })
+        );
+        assert_eq!(
+            Violation {
+                before: 97,
+                after: 75
+            }
+            .to_string(),
+            "97 must come before 75"
+        );
+    }
+
+    #[test]
+    fn test_sort() {
+        let rules = example();
+        assert_eq!(
+            rules.sort(&[75, 97, 47, 61, 53]),
+            Ok(vec![97, 75, 47, 61, 53])
+        );
+        assert_eq!(rules.sort(&[61, 13, 29]), Ok(vec![61, 29, 13]));
+        assert_eq!(
+            rules.sort(&[97, 13, 75, 29, 47]),
+            Ok(vec![97, 75, 47, 29, 13])
+        );
+        // unrelated nodes keep their order
+        assert_eq!(rules.sort(&[5, 13, 4, 61]), Ok(vec![5, 4, 61, 13]));
+        assert_eq!(rules.sort(&[13, 61, 13]), Ok(vec![61, 13, 13]));
+    }
+
+    #[test]
+    fn test_sort_is_valid() {
+        // rules that only point upwards can never form a cycle
+        let rules: Rules<u32> = (0..30)
+            .flat_map(|a| {
+                ((a + 1)..30)
+                    .filter(move |b| (a * 7 + b * 3) % 5 < 2)
+                    .map(move |b| (a, b))
+            })
+            .collect();
+        prop::check(&prop::vec(prop::int(0..=29u32), 0..=12), |sequence| {
+            let sorted = rules.sort(sequence).unwrap();
+            let mut expected = sequence.clone();
+            let mut actual = sorted.clone();
+            expected.sort();
+            actual.sort();
+            rules.is_ordered(&sorted)
+                && expected == actual
+                && (!rules.is_ordered(sequence) || sorted == *sequence)
+        });
+    }
+
+    #[test]
+    fn test_cycle() {
+        let rules: Rules<char> = [('a', 'b'), ('b', 'c'), ('c', 'd'), ('d', 'b'), ('x', 'a')]
+            .into_iter()
+            .collect();
+        let error = rules.sort(&['x', 'a', 'b', 'c', 'd']).unwrap_err();
+        assert_eq!(error.cycle, vec!['b', 'c', 'd', 'b']);
+        assert_eq!(error.to_string(), "cycle in ordering: b -> c -> d -> b");
+
+        // the cycle is outside the subset
+        assert_eq!(rules.sort(&['d', 'c', 'a']), Ok(vec!['c', 'd', 'a']));
+        assert_eq!(
+            topological_sort(&[1], |&n| [n]),
+            Err(CycleError { cycle: vec![1, 1] })
+        );
+    }
+
+    #[test]
+    fn test_topological_sort_graph() {
+        let edges = |node: &&str| match *node {
+            "shirt" => vec!["tie", "belt"],
+            "tie" => vec!["jacket"],
+            "trousers" => vec!["shoes", "belt"],
+            "belt" => vec!["jacket"],
+            _ => vec![],
+        };
+        let order = topological_sort(
+            &["jacket", "shoes", "belt", "tie", "shirt", "trousers"],
+            edges,
+        )
+        .unwrap();
+        assert_eq!(
+            order,
+            vec!["shirt", "tie", "trousers", "shoes", "belt", "jacket"]
+        );
+    }
+}
@@ -0,0 +1,375 @@
+//! Parser combinators for the usual shapes of puzzle inputs, built on `nom`.
+//!
+//! The combinators are regular `nom` parsers that use [`VerboseError`], so they compose with
+//! everything in `nom`. [`parse_input`] runs a parser over a whole input and turns a failure
+//! into a [`ParseError`] that points at the line, column and text that could not be parsed:
+//!
+//! ```ignore
+//! let equations: Vec<(i64, Vec<i64>)> = parse_input(input, lines(key_values(int, ws_list(int))))?;
+//! ```
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{
+    char, digit1, line_ending, multispace0, not_line_ending, one_of, space0, space1,
+};
+use nom::combinator::{eof, map_res, opt, peek, recognize};
+use nom::error::{context, ErrorKind, VerboseError, VerboseErrorKind};
+use nom::multi::separated_list1;
+use nom::sequence::{pair, separated_pair, terminated, tuple};
+
+use super::grid::Grid;
+
+/// Result of the parsers in this module.
+pub type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
+
+/// A parser failure, located in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the failure, starting at 1.
+    pub line: usize,
+    /// Column of the failure in characters, starting at 1.
+    pub column: usize,
+    /// What the parser expected, e.g. `integer` or `` `,` ``.
+    pub expected: String,
+    /// Remainder of the line at the failure, empty at the end of the input.
+    pub found: String,
+}
+
+impl ParseError {
+    fn new(input: &str, remaining: &str, expected: String) -> Self {
+        let consumed = &input[..input.len() - remaining.len()];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+
+        Self {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            expected,
+            found: remaining.lines().next().unwrap_or_default().to_string(),
+        }
+    }
+
+    fn from_nom(input: &str, error: VerboseError<&str>) -> Self {
+        let Some((remaining, _)) = error.errors.first() else {
+            return Self::new(input, input, "valid input".into());
+        };
+
+        // the innermost context names the failing item best, e.g. `integer` instead of `digit`,
+        // except for a `literal` which names its expected text.
+        let expected = match error.errors.as_slice() {
+            [(at, VerboseErrorKind::Nom(ErrorKind::Tag)), (context_at, VerboseErrorKind::Context(text)), ..]
+                if at == context_at =>
+            {
+                format!("`{text}`")
+            }
+            errors => errors
+                .iter()
+                .find_map(|(_, kind)| match kind {
+                    VerboseErrorKind::Context(context) => Some(context.to_string()),
+                    _ => None,
+                })
+                .unwrap_or_else(|| match &errors[0].1 {
+                    VerboseErrorKind::Char(c) => format!("`{}`", c.escape_default()),
+                    VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+                    VerboseErrorKind::Context(context) => context.to_string(),
+                }),
+        };
+
+        Self::new(input, remaining, expected)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            write!(f, ", found end of line")
+        } else {
+            write!(f, ", found `{}`", self.found)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs `parser` over the whole `input`, allowing trailing whitespace.
+pub fn parse_input<'a, T>(
+    input: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> Result<T, ParseError> {
+    let mut parser = terminated(parser, context("end of input", pair(multispace0, eof)));
+
+    match parser(input) {
+        Ok((_, value)) => Ok(value),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::from_nom(input, e)),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::new(input, "", "more input".into())),
+    }
+}
+
+/// The exact `text`, which errors name as expected, e.g. ``expected `, Y+` ``.
+pub fn literal<'a>(text: &'static str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    context(text, tag(text))
+}
+
+/// An integer with an optional sign, e.g. `-42`.
+pub fn int<T: FromStr>(input: &str) -> IResult<'_, T> {
+    context(
+        "integer",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse),
+    )(input)
+}
+
+/// Items separated by spaces or tabs on a single line, e.g. `1 2  3`.
+pub fn ws_list<'a, T>(
+    item: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(space1, item)
+}
+
+/// Items separated by commas and optional spaces, e.g. `1,2, 3`.
+pub fn comma_list<'a, T>(
+    item: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(tuple((space0, char(','), space0)), item)
+}
+
+/// A key and its values separated by a colon, e.g. `190: 10 19`.
+pub fn key_values<'a, K, V>(
+    key: impl FnMut(&'a str) -> IResult<'a, K>,
+    values: impl FnMut(&'a str) -> IResult<'a, V>,
+) -> impl FnMut(&'a str) -> IResult<'a, (K, V)> {
+    separated_pair(key, pair(char(':'), space0), values)
+}
+
+/// Returns the input after the line ending if the next line is not empty.
+fn next_line(input: &str) -> Option<&str> {
+    let (rest, _) = line_ending::<_, ()>(input).ok()?;
+    (!rest.is_empty() && line_ending::<_, ()>(rest).is_err()).then_some(rest)
+}
+
+/// Returns the input after a blank line if another block follows.
+fn next_block(input: &str) -> Option<&str> {
+    let (rest, _) = line_ending::<_, ()>(input).ok()?;
+    let (mut rest, _) = line_ending::<_, ()>(rest).ok()?;
+    while let Ok((after, _)) = line_ending::<_, ()>(rest) {
+        rest = after;
+    }
+    (!rest.is_empty()).then_some(rest)
+}
+
+/// One `line` per line until a blank line or the end of the input.
+/// Each line has to be parsed completely, so failures point at the offending text.
+pub fn lines<'a, T>(
+    mut line: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    move |input| {
+        let mut end_of_line = context("end of line", peek(alt((line_ending, eof))));
+
+        let (mut rest, first) = line(input)?;
+        (rest, _) = end_of_line(rest).map_err(fail)?;
+
+        let mut items = vec![first];
+        while let Some(next) = next_line(rest) {
+            let (after, item) = line(next).map_err(fail)?;
+            (rest, _) = end_of_line(after).map_err(fail)?;
+            items.push(item);
+        }
+        Ok((rest, items))
+    }
+}
+
+/// One `block` per group of lines, with groups separated by blank lines.
+pub fn blocks<'a, T>(
+    mut block: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    move |input| {
+        let (mut rest, first) = block(input)?;
+
+        let mut items = vec![first];
+        while let Some(next) = next_block(rest) {
+            let (after, item) = block(next).map_err(fail)?;
+            rest = after;
+            items.push(item);
+        }
+        Ok((rest, items))
+    }
+}
+
+/// Turns a recoverable error into a failure, once the input is known to continue with an item.
+fn fail<E>(error: nom::Err<E>) -> nom::Err<E> {
+    match error {
+        nom::Err::Error(e) => nom::Err::Failure(e),
+        e => e,
+    }
+}
+
+/// A rectangular grid of characters until a blank line or the end of the input.
+pub fn grid(input: &str) -> IResult<'_, Grid<char>> {
+    let non_empty_line = take_while1(|c| c != '\n' && c != '\r');
+    context(
+        "rectangular grid",
+        map_res(recognize(lines(non_empty_line)), str::parse),
+    )(input)
+}
+
+/// The remainder of the current line.
+pub fn rest_of_line(input: &str) -> IResult<'_, &str> {
+    not_line_ending(input)
+}
+
+/// Extracts all integers from `text`, ignoring everything in between,
+/// e.g. `[0, 4, 3, -3]` from `p=0,4 v=3,-3`.
+///
+/// A `-` is read as a sign unless it directly follows a letter or digit, so `1-3` yields `[1, 3]`.
+pub fn integers<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = text.as_bytes();
+    let mut values = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let signed = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric());
+        if !signed && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let number = &text[start..i];
+        let value = number.parse().map_err(|_| {
+            ParseError::new(
+                text,
+                &text[start..],
+                format!("integer that fits into {}", std::any::type_name::<T>()),
+            )
+        })?;
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::sequence::preceded;
+
+    #[test]
+    fn test_int() {
+        assert_eq!(int::<i64>("-42 rest"), Ok((" rest", -42)));
+        assert_eq!(int::<u8>("+7"), Ok(("", 7)));
+        assert!(int::<u8>("256").is_err());
+        assert!(int::<i32>("x").is_err());
+    }
+
+    #[test]
+    fn test_lists() {
+        assert_eq!(
+            parse_input("1 2\t 3", ws_list(int::<u32>)),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(
+            parse_input("75,47, 61", comma_list(int::<u32>)),
+            Ok(vec![75, 47, 61])
+        );
+        assert_eq!(
+            parse_input("190: 10 19\n", key_values(int::<u64>, ws_list(int))),
+            Ok((190, vec![10, 19]))
+        );
+    }
+
+    #[test]
+    fn test_lines_and_blocks() {
+        let rule = || separated_pair(int::<u32>, char('|'), int::<u32>);
+        let input = "47|53\n97|13\n\n75,47\n97\n";
+        assert_eq!(
+            parse_input(
+                input,
+                separated_pair(lines(rule()), tag("\n\n"), lines(comma_list(int::<u32>)))
+            ),
+            Ok((vec![(47, 53), (97, 13)], vec![vec![75, 47], vec![97]]))
+        );
+
+        let input = "1\n2\n\n\n3\r\n4\n\n";
+        assert_eq!(
+            parse_input(input, blocks(lines(int::<u32>))),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let (rest, parsed) = grid("ab\ncd\n\nxy").unwrap();
+        assert_eq!(parsed.to_string(), "ab\ncd\n");
+        assert_eq!(rest, "\n\nxy");
+
+        let err = parse_input("ab\nc\n", grid).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.expected, "rectangular grid");
+    }
+
+    #[test]
+    fn test_error_location() {
+        let err = parse_input("1 2\n3 x 5\n", lines(ws_list(int::<u32>))).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError {
+                line: 2,
+                column: 2,
+                expected: "end of line".into(),
+                found: " x 5".into(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected end of line, found ` x 5`"
+        );
+
+        let button = || preceded(literal("Button A: X+"), int::<i64>);
+        let err = parse_input("Button A: X+a", button()).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 13));
+        assert_eq!(err.expected, "integer");
+        let err = parse_input("Button B: X+1", button()).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (1, "`Button A: X+`"));
+        let err =
+            parse_input("Button B: X+1", preceded(tag("Button A: X+"), int::<i64>)).unwrap_err();
+        assert_eq!(err.expected, "tag");
+
+        let err = parse_input("1,2,", comma_list(int::<u8>)).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (4, "end of input"));
+
+        let err = parse_input(
+            "1|2\n3",
+            lines(separated_pair(int::<u8>, char('|'), int::<u8>)),
+        )
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "`|`");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected `|`, found end of line"
+        );
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(integers::<i64>("p=0,4 v=3,-3"), Ok(vec![0, 4, 3, -3]));
+        assert_eq!(integers::<u32>("1-3, x2: 40"), Ok(vec![1, 3, 2, 40]));
+        assert_eq!(integers::<i32>("no numbers - here"), Ok(vec![]));
+
+        let err = integers::<u8>("a\nb 300").unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 3, "300"));
+    }
+}
@@ -0,0 +1,477 @@
+//! Two-dimensional vectors and compass directions.
+//!
+//! [`Vec2`] uses screen coordinates: `x` grows to the right and `y` grows downwards, so
+//! [`Direction::Up`] is `(0, -1)`. This matches the [`Grid`](super::grid::Grid), where a
+//! `Vec2` converts to the position `(row, col) = (y, x)`.
+
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+use super::grid::{Offset, Pos};
+
+/// Signed integers that can be used as coordinates of a [`Vec2`].
+pub trait Signed:
+    Copy
+    + Debug
+    + Default
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Signed for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                <$t>::rem_euclid(self, rhs)
+            }
+        }
+
+        impl From<Pos> for Vec2<$t> {
+            /// # Panics
+            /// Panics if the position does not fit into the coordinate type.
+            fn from((row, col): Pos) -> Self {
+                let convert = |n: usize| {
+                    <$t>::try_from(n).unwrap_or_else(|_| panic!("{n} does not fit into a coordinate"))
+                };
+                Vec2::new(convert(col), convert(row))
+            }
+        }
+
+        impl TryFrom<Vec2<$t>> for Pos {
+            type Error = std::num::TryFromIntError;
+
+            /// Fails for vectors with a negative coordinate.
+            fn try_from(v: Vec2<$t>) -> Result<Self, Self::Error> {
+                Ok((usize::try_from(v.y)?, usize::try_from(v.x)?))
+            }
+        }
+    )*};
+}
+
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+/// A vector or position with `x` and `y` coordinates.
+///
+/// The derived ordering is lexicographic, `x` first and then `y`, so that vectors can be sorted
+/// and kept in a `BTreeSet`. It is not a component-wise comparison: `(0, 5) < (1, 0)`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Signed> Vec2<T> {
+    pub const ZERO: Self = Self::new(T::ZERO, T::ZERO);
+
+    /// A vector with both coordinates set to `value`.
+    pub fn splat(value: T) -> Self {
+        Self::new(value, value)
+    }
+
+    /// Sum of the absolute coordinates.
+    pub fn manhattan(self) -> T {
+        self.x.abs() + self.y.abs()
+    }
+
+    /// Largest absolute coordinate, i.e. the number of king moves to reach the vector.
+    pub fn chebyshev(self) -> T {
+        self.x.abs().max(self.y.abs())
+    }
+
+    pub fn manhattan_to(self, other: Self) -> T {
+        (self - other).manhattan()
+    }
+
+    pub fn chebyshev_to(self, other: Self) -> T {
+        (self - other).chebyshev()
+    }
+
+    /// Wraps the vector into the rectangle from the origin to `size` (exclusive).
+    pub fn rem_euclid(self, size: Self) -> Self {
+        Self::new(self.x.rem_euclid(size.x), self.y.rem_euclid(size.y))
+    }
+
+    /// Orthogonal neighbours in the order up, right, down, left.
+    pub fn neighbours(self) -> [Self; 4] {
+        Direction::ORTHOGONAL.map(|dir| self + dir.vector())
+    }
+
+    /// All eight neighbours, clockwise starting with up.
+    pub fn neighbours8(self) -> [Self; 8] {
+        Direction::ALL.map(|dir| self + dir.vector())
+    }
+}
+
+impl<T: Signed + TryFrom<usize>> Vec2<T>
+where
+    usize: TryFrom<T>,
+{
+    /// Row-major index of the vector in a grid of `width` columns and `height` rows,
+    /// or `None` if the vector lies outside of it.
+    pub fn to_index(self, width: usize, height: usize) -> Option<usize> {
+        let (x, y) = (usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?);
+        (x < width && y < height).then(|| y * width + x)
+    }
+
+    /// Vector of the cell at the row-major `index` of a grid of `width` columns.
+    ///
+    /// # Panics
+    /// Panics if the coordinates do not fit into `T`.
+    pub fn from_index(index: usize, width: usize) -> Self {
+        let convert = |n: usize| {
+            T::try_from(n).unwrap_or_else(|_| panic!("{n} does not fit into a coordinate"))
+        };
+        Self::new(convert(index % width), convert(index / width))
+    }
+}
+
+impl<T: Debug> Debug for Vec2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({:?}, {:?})", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Vec2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Offset of the grid, i.e. `(y, x)`.
+impl From<Vec2<isize>> for Offset {
+    fn from(v: Vec2<isize>) -> Self {
+        (v.y, v.x)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec2<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// Component-wise product.
+impl<T: Mul<Output = T>> Mul for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(self.x * other.x, self.y * other.y)
+    }
+}
+
+/// Component-wise quotient.
+impl<T: Div<Output = T>> Div for Vec2<T> {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        Self::new(self.x / other.x, self.y / other.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vec2<T> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        Self::new(self.x * scalar, self.y * scalar)
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for Vec2<T> {
+    type Output = Self;
+
+    fn div(self, scalar: T) -> Self {
+        Self::new(self.x / scalar, self.y / scalar)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Vec2<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> MulAssign<T> for Vec2<T> {
+    fn mul_assign(&mut self, scalar: T) {
+        *self = *self * scalar;
+    }
+}
+
+impl<T: Copy + Div<Output = T>> DivAssign<T> for Vec2<T> {
+    fn div_assign(&mut self, scalar: T) {
+        *self = *self / scalar;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the eight compass directions, in screen coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The four orthogonal directions, clockwise starting with up.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The four diagonal directions, clockwise starting with up-right.
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::UpRight,
+        Direction::DownRight,
+        Direction::DownLeft,
+        Direction::UpLeft,
+    ];
+
+    /// All eight directions, clockwise starting with up.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// Position in [`Direction::ALL`], i.e. the number of 45° steps clockwise from up.
+    fn eighths(self) -> usize {
+        self as usize
+    }
+
+    fn rotate(self, eighths: usize) -> Direction {
+        Direction::ALL[(self.eighths() + eighths) % 8]
+    }
+
+    /// Rotates 90° clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// Rotates 90° counterclockwise.
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    /// Rotates 45° clockwise.
+    pub fn turn_right_45(self) -> Direction {
+        self.rotate(1)
+    }
+
+    /// Rotates 45° counterclockwise.
+    pub fn turn_left_45(self) -> Direction {
+        self.rotate(7)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub fn is_orthogonal(self) -> bool {
+        self.eighths().is_multiple_of(2)
+    }
+
+    /// Unit step in this direction, e.g. `(1, -1)` for up-right.
+    pub fn vector<T: Signed>(self) -> Vec2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        let (x, y) = match self {
+            Direction::Up => (zero, -one),
+            Direction::UpRight => (one, -one),
+            Direction::Right => (one, zero),
+            Direction::DownRight => (one, one),
+            Direction::Down => (zero, one),
+            Direction::DownLeft => (-one, one),
+            Direction::Left => (-one, zero),
+            Direction::UpLeft => (-one, -one),
+        };
+        Vec2::new(x, y)
+    }
+
+    /// Unit step in this direction as an offset of the grid.
+    pub fn offset(self) -> Offset {
+        self.vector::<isize>().into()
+    }
+}
+
+/// Parses the arrows `^`, `>`, `v` and `<`.
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' => Ok(Direction::Up),
+            '>' => Ok(Direction::Right),
+            'v' => Ok(Direction::Down),
+            '<' => Ok(Direction::Left),
+            _ => Err(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operators() {
+        let (a, b) = (Vec2::new(3, -2), Vec2::new(1, 4));
+        assert_eq!(a + b, Vec2::new(4, 2));
+        assert_eq!(a - b, Vec2::new(2, -6));
+        assert_eq!(a * b, Vec2::new(3, -8));
+        assert_eq!(a / b, Vec2::new(3, 0));
+        assert_eq!(a * 3, Vec2::new(9, -6));
+        assert_eq!(a / 2, Vec2::new(1, -1));
+        assert_eq!(-a, Vec2::new(-3, 2));
+
+        let mut c = a;
+        c += b;
+        c -= Vec2::splat(1);
+        c *= 2;
+        c /= 3;
+        assert_eq!(c, Vec2::new(2, 0));
+    }
+
+    #[test]
+    fn test_distances() {
+        let v = Vec2::new(-3i64, 5);
+        assert_eq!(v.manhattan(), 8);
+        assert_eq!(v.chebyshev(), 5);
+        assert_eq!(v.manhattan_to(Vec2::new(1, 1)), 8);
+        assert_eq!(v.chebyshev_to(Vec2::new(1, 1)), 4);
+        assert_eq!(Vec2::<i64>::ZERO.manhattan(), 0);
+    }
+
+    #[test]
+    fn test_rem_euclid() {
+        let size = Vec2::new(11, 7);
+        assert_eq!(Vec2::new(-1, 15).rem_euclid(size), Vec2::new(10, 1));
+        assert_eq!(Vec2::new(-22, -7).rem_euclid(size), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let v = Vec2::new(0, 0);
+        assert_eq!(
+            v.neighbours(),
+            [
+                Vec2::new(0, -1),
+                Vec2::new(1, 0),
+                Vec2::new(0, 1),
+                Vec2::new(-1, 0)
+            ]
+        );
+        assert_eq!(v.neighbours8()[1], Vec2::new(1, -1));
+        assert!(v.neighbours8().iter().all(|n| n.chebyshev() == 1));
+    }
+
+    #[test]
+    fn test_grid_conversions() {
+        let v = Vec2::<i32>::from((2, 5));
+        assert_eq!(v, Vec2::new(5, 2));
+        assert_eq!(Pos::try_from(v), Ok((2, 5)));
+        assert!(Pos::try_from(Vec2::new(-1, 0)).is_err());
+
+        assert_eq!(Vec2::new(2i64, 3).to_index(10, 4), Some(32));
+        assert_eq!(Vec2::new(10i64, 3).to_index(10, 4), None);
+        assert_eq!(Vec2::new(-1i64, 3).to_index(10, 4), None);
+        assert_eq!(Vec2::new(2i64, 4).to_index(10, 4), None);
+        assert_eq!(Vec2::new(2i64, -1).to_index(10, 4), None);
+        assert_eq!(Vec2::<i64>::from_index(32, 10), Vec2::new(2, 3));
+
+        assert_eq!(Direction::UpRight.offset(), (-1, 1));
+        assert_eq!(Offset::from(Vec2::new(4isize, -2)), (-2, 4));
+    }
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpLeft.turn_right(), Direction::UpRight);
+        assert_eq!(Direction::Up.turn_right_45(), Direction::UpRight);
+        assert_eq!(Direction::Up.turn_left_45(), Direction::UpLeft);
+        assert_eq!(Direction::DownRight.reverse(), Direction::UpLeft);
+
+        for dir in Direction::ALL {
+            assert_eq!(dir.reverse().vector::<i32>(), -dir.vector::<i32>());
+            assert_eq!(dir.turn_left().turn_right(), dir);
+        }
+    }
+
+    #[test]
+    fn test_iteration() {
+        assert!(Direction::ORTHOGONAL.iter().all(|dir| dir.is_orthogonal()));
+        assert!(Direction::DIAGONAL.iter().all(|dir| !dir.is_orthogonal()));
+        assert_eq!(
+            Direction::ALL
+                .iter()
+                .map(|dir| dir.vector::<i32>())
+                .fold(Vec2::ZERO, |sum, v| sum + v),
+            Vec2::ZERO
+        );
+        assert_eq!(Direction::try_from('v'), Ok(Direction::Down));
+        assert_eq!(Direction::try_from('x'), Err('x'));
+    }
+}
@@ -0,0 +1,448 @@
+//! A small property-based testing harness.
+//!
+//! A property is checked against many inputs drawn from a [`Gen`]erator with a seeded [`Rng`],
+//! so that every failure can be reproduced. When a property fails, its input is shrunk to a
+//! minimal input that still fails, which is reported together with the seed.
+//!
+//! The number of cases and the seed can be set with the `PROP_CASES` and `PROP_SEED`
+//! environment variables, e.g. `PROP_SEED=42 cargo test --bin 11`.
+
+use std::env;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+
+/// Seed used when `PROP_SEED` is not set, so test runs are reproducible by default.
+pub const DEFAULT_SEED: u64 = 0x5EED_2024;
+/// Number of cases checked per property when `PROP_CASES` is not set.
+pub const DEFAULT_CASES: usize = 1000;
+/// Upper bound of successful shrink steps, guards against shrinkers that never converge.
+const MAX_SHRINKS: usize = 10_000;
+/// Upper bound of attempts to generate a value that passes a [`Gen::filter`].
+const MAX_FILTER_ATTEMPTS: usize = 10_000;
+
+/// A seeded pseudo random number generator (SplitMix64).
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `range`, including both ends.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (lo, hi) = range.into_inner();
+        assert!(lo <= hi, "empty range {lo}..={hi}");
+
+        // NOTE: the span of two i64 always fits into an u64.
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+        match hi.wrapping_sub(lo) as u64 {
+            u64::MAX => self.next_u64() as i64,
+            span => lo.wrapping_add((self.next_u64() % (span + 1)) as i64),
+        }
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Returns a random element of a non-empty slice.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        // NOTE: slices are far shorter than `i64::MAX`.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let i = self.range(0..=items.len() as i64 - 1) as usize;
+        &items[i]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+type Shrinker<T> = Rc<dyn Fn(&T) -> Vec<T>>;
+
+/// Generates random values of `T` and shrinks them to simpler values.
+pub struct Gen<T> {
+    generate: Rc<dyn Fn(&mut Rng) -> T>,
+    shrink: Shrinker<T>,
+}
+
+impl<T> Clone for Gen<T> {
+    fn clone(&self) -> Self {
+        Self {
+            generate: Rc::clone(&self.generate),
+            shrink: Rc::clone(&self.shrink),
+        }
+    }
+}
+
+impl<T: 'static> Gen<T> {
+    /// Creates a generator. `shrink` returns simpler candidates for a value, simplest first.
+    pub fn new(
+        generate: impl Fn(&mut Rng) -> T + 'static,
+        shrink: impl Fn(&T) -> Vec<T> + 'static,
+    ) -> Self {
+        Self {
+            generate: Rc::new(generate),
+            shrink: Rc::new(shrink),
+        }
+    }
+
+    /// Creates a generator whose values are not shrunk.
+    pub fn from_fn(generate: impl Fn(&mut Rng) -> T + 'static) -> Self {
+        Self::new(generate, |_| vec![])
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> T {
+        (self.generate)(rng)
+    }
+
+    pub fn shrink(&self, value: &T) -> Vec<T> {
+        (self.shrink)(value)
+    }
+
+    /// Only generates and shrinks to values that satisfy `predicate`.
+    pub fn filter(self, predicate: impl Fn(&T) -> bool + 'static) -> Self {
+        let predicate = Rc::new(predicate);
+        let keep = Rc::clone(&predicate);
+        let source = self.clone();
+
+        Self::new(
+            move |rng| {
+                (0..MAX_FILTER_ATTEMPTS)
+                    .map(|_| source.generate(rng))
+                    .find(|value| predicate(value))
+                    .expect("filter rejected too many generated values")
+            },
+            move |value| {
+                self.shrink(value)
+                    .into_iter()
+                    .filter(|value| keep(value))
+                    .collect()
+            },
+        )
+    }
+}
+
+/// Generates integers in `range`, shrinking towards zero or the end of the range closest to it.
+/// The range has to fit into an `i64`.
+pub fn int<N>(range: RangeInclusive<N>) -> Gen<N>
+where
+    N: Copy + TryFrom<i64> + TryInto<i64> + 'static,
+{
+    let to_i64 = |n: N| n.try_into().ok().expect("range does not fit into i64");
+    let from_i64 = |n: i64| N::try_from(n).ok().unwrap();
+    let (lo, hi) = (to_i64(*range.start()), to_i64(*range.end()));
+
+    Gen::new(
+        move |rng| from_i64(rng.range(lo..=hi)),
+        move |value| {
+            shrink_int(to_i64(*value), 0.clamp(lo, hi))
+                .into_iter()
+                .map(from_i64)
+                .collect()
+        },
+    )
+}
+
+/// Candidates between `target` and `value`, closest to `target` first.
+fn shrink_int(value: i64, target: i64) -> Vec<i64> {
+    let mut candidates = vec![];
+    let mut distance = value - target;
+
+    while distance != 0 {
+        let candidate = value - distance;
+        if candidates.last() != Some(&candidate) {
+            candidates.push(candidate);
+        }
+        distance /= 2;
+    }
+
+    candidates
+}
+
+/// Generates vectors with a length in `len`, shrinking by removing and by shrinking elements.
+pub fn vec<T: Clone + 'static>(element: Gen<T>, len: RangeInclusive<usize>) -> Gen<Vec<T>> {
+    let (min_len, max_len) = len.into_inner();
+    let shrink_element = element.clone();
+
+    Gen::new(
+        move |rng| {
+            // NOTE: lengths of generated vectors are small.
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                clippy::cast_possible_wrap
+            )]
+            let len = rng.range(min_len as i64..=max_len as i64) as usize;
+            (0..len).map(|_| element.generate(rng)).collect()
+        },
+        move |values: &Vec<T>| {
+            let mut candidates = vec![];
+
+            // remove chunks, from halves down to single elements
+            let mut chunk = values.len() / 2;
+            while chunk > 0 {
+                if values.len() - chunk >= min_len {
+                    for start in (0..=values.len() - chunk).step_by(chunk) {
+                        let mut candidate = values[..start].to_vec();
+                        candidate.extend_from_slice(&values[start + chunk..]);
+                        candidates.push(candidate);
+                    }
+                }
+                chunk /= 2;
+            }
+            if values.len() == 1 && min_len == 0 {
+                candidates.push(vec![]);
+            }
+
+            for (i, value) in values.iter().enumerate() {
+                for shrunk in shrink_element.shrink(value) {
+                    let mut candidate = values.clone();
+                    candidate[i] = shrunk;
+                    candidates.push(candidate);
+                }
+            }
+
+            candidates
+        },
+    )
+}
+
+/// Generates pairs, shrinking one side at a time.
+pub fn zip<A: Clone + 'static, B: Clone + 'static>(a: Gen<A>, b: Gen<B>) -> Gen<(A, B)> {
+    let (shrink_a, shrink_b) = (a.clone(), b.clone());
+
+    Gen::new(
+        move |rng| (a.generate(rng), b.generate(rng)),
+        move |(x, y)| {
+            let mut candidates: Vec<(A, B)> = shrink_a
+                .shrink(x)
+                .into_iter()
+                .map(|x| (x, y.clone()))
+                .collect();
+            candidates.extend(shrink_b.shrink(y).into_iter().map(|y| (x.clone(), y)));
+            candidates
+        },
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Settings of a property check.
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+}
+
+impl Default for Config {
+    /// Reads `PROP_CASES` and `PROP_SEED` from the environment.
+    fn default() -> Self {
+        let var = |name: &str| env::var(name).ok().and_then(|v| v.parse().ok());
+        Self {
+            cases: var("PROP_CASES").map_or(DEFAULT_CASES, |cases: u64| {
+                usize::try_from(cases).unwrap_or(DEFAULT_CASES)
+            }),
+            seed: var("PROP_SEED").unwrap_or(DEFAULT_SEED),
+        }
+    }
+}
+
+/// A failing input, before and after shrinking.
+#[derive(Debug)]
+struct Failure<T> {
+    case: usize,
+    original: T,
+    minimal: T,
+    shrinks: usize,
+    reason: String,
+}
+
+/// Checks that `property` holds for every generated value, panicking with the minimal failing input.
+pub fn check<T: Clone + Debug + 'static>(gen: &Gen<T>, property: impl Fn(&T) -> bool) {
+    check_with(Config::default(), gen, property);
+}
+
+pub fn check_with<T: Clone + Debug + 'static>(
+    config: Config,
+    gen: &Gen<T>,
+    property: impl Fn(&T) -> bool,
+) {
+    report(
+        config,
+        find_failure(config, gen, |value| {
+            (!property(value)).then(|| "property does not hold".into())
+        }),
+    );
+}
+
+/// Checks that two implementations return the same output for every generated value.
+pub fn agree<T, R>(gen: &Gen<T>, a: impl Fn(&T) -> R, b: impl Fn(&T) -> R)
+where
+    T: Clone + Debug + 'static,
+    R: PartialEq + Debug,
+{
+    let config = Config::default();
+    report(
+        config,
+        find_failure(config, gen, |value| {
+            let (x, y) = (a(value), b(value));
+            (x != y).then(|| format!("implementations disagree: {x:?} != {y:?}"))
+        }),
+    );
+}
+
+fn report<T: Debug>(config: Config, failure: Option<Failure<T>>) {
+    if let Some(failure) = failure {
+        panic!(
+            "{}\n  minimal input: {:?} (after {} shrinks)\n  original input: {:?} (case {} of seed {})\n  reproduce with PROP_SEED={}",
+            failure.reason,
+            failure.minimal,
+            failure.shrinks,
+            failure.original,
+            failure.case + 1,
+            config.seed,
+            config.seed
+        );
+    }
+}
+
+/// Runs the cases of a check. `fails` returns why a value fails, panics count as failures.
+fn find_failure<T: Clone + 'static>(
+    config: Config,
+    gen: &Gen<T>,
+    fails: impl Fn(&T) -> Option<String>,
+) -> Option<Failure<T>> {
+    let fails = |value: &T| match catch_unwind(AssertUnwindSafe(|| fails(value))) {
+        Ok(reason) => reason,
+        Err(payload) => Some(format!(
+            "panicked: {}",
+            payload
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default()
+        )),
+    };
+
+    let mut rng = Rng::new(config.seed);
+    let (case, original, mut reason) = (0..config.cases).find_map(|case| {
+        let value = gen.generate(&mut rng);
+        fails(&value).map(|reason| (case, value, reason))
+    })?;
+
+    let mut minimal = original.clone();
+    let mut shrinks = 0;
+    'shrink: while shrinks < MAX_SHRINKS {
+        for candidate in gen.shrink(&minimal) {
+            if let Some(candidate_reason) = fails(&candidate) {
+                minimal = candidate;
+                reason = candidate_reason;
+                shrinks += 1;
+                continue 'shrink;
+            }
+        }
+        break;
+    }
+
+    Some(Failure {
+        case,
+        original,
+        minimal,
+        shrinks,
+        reason,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            cases: 1000,
+            seed: DEFAULT_SEED,
+        }
+    }
+
+    #[test]
+    fn test_rng_is_seeded() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn test_rng_range() {
+        let mut rng = Rng::new(1);
+        let values: Vec<i64> = (0..1000).map(|_| rng.range(-3..=3)).collect();
+        assert!(values.iter().all(|v| (-3..=3).contains(v)));
+        assert!((-3..=3).all(|v| values.contains(&v)));
+        assert_eq!(rng.range(5..=5), 5);
+        rng.range(i64::MIN..=i64::MAX);
+    }
+
+    #[test]
+    fn test_shrink_int() {
+        assert_eq!(shrink_int(10, 0), vec![0, 5, 8, 9]);
+        assert_eq!(shrink_int(-3, 0), vec![0, -2]);
+        assert_eq!(shrink_int(4, 4), Vec::<i64>::new());
+        assert_eq!(int(5..=9u32).shrink(&7), vec![5, 6]);
+    }
+
+    #[test]
+    fn test_shrinks_to_minimal_input() {
+        let gen = vec(int(0..=1000), 0..=20);
+        let failure = find_failure(config(), &gen, |v: &Vec<i64>| {
+            (v.iter().sum::<i64>() > 100).then(|| "too large".into())
+        })
+        .unwrap();
+
+        assert_eq!(failure.minimal, vec![101]);
+        assert!(failure.original.iter().sum::<i64>() > 100);
+    }
+
+    #[test]
+    fn test_catches_panics() {
+        let gen = zip(int(0..=10), int(0..=10));
+        let failure = find_failure(config(), &gen, |(a, b): &(i32, i32)| {
+            assert!(a - b < 5, "difference too large");
+            None
+        })
+        .unwrap();
+
+        assert_eq!(failure.minimal, (5, 0));
+        assert!(failure.reason.contains("difference too large"));
+    }
+
+    #[test]
+    fn test_filter() {
+        let gen = int(0..=100).filter(|v: &i64| v % 2 == 1);
+        let mut rng = Rng::new(3);
+        assert!((0..100).all(|_| gen.generate(&mut rng) % 2 == 1));
+        assert_eq!(gen.shrink(&9), vec![5, 7]);
+    }
+
+    #[test]
+    fn test_passing_properties() {
+        check(&vec(int(-50..=50), 0..=10), |v| {
+            let mut sorted = v.clone();
+            sorted.sort_unstable();
+            sorted.len() == v.len()
+        });
+        agree(&int(0..=1000u64), |n| n * 2, |n| n + n);
+    }
+}
@@ -4,71 +4,69 @@
 //! disjoint (non-overlapping) sets.
 //! It provides near-constant-time operations to add new sets, to find the
 //! representative of a set, and to merge sets.
+//!
+//! [`DenseUnionFind`] works on the indices `0..len` and is backed only by vectors, so it is
+//! the one to use for grid cells and other elements that are already numbered.
+//! [`UnionFind`] maps arbitrary elements to such indices.
 
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 
-#[derive(Debug)]
-pub struct UnionFind<T: Debug + Eq + Hash> {
-    payloads: HashMap<T, usize>, // Maps values to their indices in the parent_links array.
-    parent_links: Vec<usize>,    // Holds the parent pointers; root elements are their own parents.
-    sizes: Vec<usize>,           // Holds the sizes of the sets.
-    count: usize,                // Number of disjoint sets.
+#[derive(Debug, Clone, Default)]
+pub struct DenseUnionFind {
+    parent_links: Vec<usize>, // Holds the parent pointers; root elements are their own parents.
+    sizes: Vec<usize>,        // Holds the sizes of the sets, valid for roots only.
+    count: usize,             // Number of disjoint sets.
 }
 
-impl<T: Debug + Eq + Hash> UnionFind<T> {
-    /// Creates an empty Union-Find structure with a specified capacity.
-    pub fn with_capacity(capacity: usize) -> Self {
+impl DenseUnionFind {
+    /// Creates `len` singleton sets with the indices `0..len`.
+    pub fn new(len: usize) -> Self {
         Self {
-            parent_links: Vec::with_capacity(capacity),
-            sizes: Vec::with_capacity(capacity),
-            payloads: HashMap::with_capacity(capacity),
-            count: 0,
+            parent_links: (0..len).collect(),
+            sizes: vec![1; len],
+            count: len,
         }
     }
 
-    /// Inserts a new item (disjoint set) into the data structure.
-    pub fn insert(&mut self, item: T) {
-        let key = self.payloads.len();
-        self.parent_links.push(key);
-        self.sizes.push(1);
-        self.payloads.insert(item, key);
-        self.count += 1;
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.parent_links.len()
     }
 
-    /// Returns the root index of the set containing the given value, or `None` if it doesn't exist.
-    pub fn find(&mut self, value: &T) -> Option<usize> {
-        self.payloads
-            .get(value)
-            .copied()
-            .map(|key| self.find_by_key(key))
+    pub fn is_empty(&self) -> bool {
+        self.parent_links.is_empty()
     }
 
-    /// Unites the sets containing the two given values. Returns:
-    /// - `None` if either value hasn't been inserted,
-    /// - `Some(true)` if two disjoint sets have been merged,
-    /// - `Some(false)` if both elements were already in the same set.
-    pub fn union(&mut self, first_item: &T, sec_item: &T) -> Option<bool> {
-        let (first_root, sec_root) = (self.find(first_item), self.find(sec_item));
-        match (first_root, sec_root) {
-            (Some(first_root), Some(sec_root)) => Some(self.union_by_key(first_root, sec_root)),
-            _ => None,
-        }
+    /// Adds a new singleton set and returns its index.
+    pub fn push(&mut self) -> usize {
+        let index = self.len();
+        self.parent_links.push(index);
+        self.sizes.push(1);
+        self.count += 1;
+        index
     }
 
-    /// Finds the root of the set containing the element with the given index.
-    fn find_by_key(&mut self, key: usize) -> usize {
-        if self.parent_links[key] != key {
-            self.parent_links[key] = self.find_by_key(self.parent_links[key]);
+    /// Returns the root index of the set containing `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn find(&mut self, mut index: usize) -> usize {
+        // path halving: point every other node on the way at its grandparent
+        while self.parent_links[index] != index {
+            let grandparent = self.parent_links[self.parent_links[index]];
+            self.parent_links[index] = grandparent;
+            index = grandparent;
         }
-        self.parent_links[key]
+        index
     }
 
-    /// Unites the sets containing the two elements identified by their indices.
-    fn union_by_key(&mut self, first_key: usize, sec_key: usize) -> bool {
-        let (first_root, sec_root) = (self.find_by_key(first_key), self.find_by_key(sec_key));
+    /// Unites the sets containing the two indices, attaching the smaller set to the larger one.
+    /// Returns `false` if they were already in the same set.
+    pub fn union(&mut self, first: usize, second: usize) -> bool {
+        let (first_root, sec_root) = (self.find(first), self.find(second));
 
         if first_root == sec_root {
             return false;
@@ -89,6 +87,67 @@ impl<T: Debug + Eq + Hash> UnionFind<T> {
         true
     }
 
+    /// Checks if two indices belong to the same set.
+    pub fn is_same_set(&mut self, first: usize, second: usize) -> bool {
+        self.find(first) == self.find(second)
+    }
+
+    /// Number of elements in the set containing `index`.
+    pub fn set_size(&mut self, index: usize) -> usize {
+        let root = self.find(index);
+        self.sizes[root]
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[derive(Debug)]
+pub struct UnionFind<T: Debug + Eq + Hash> {
+    payloads: HashMap<T, usize>, // Maps values to their indices in `sets`.
+    sets: DenseUnionFind,
+}
+
+impl<T: Debug + Eq + Hash> UnionFind<T> {
+    /// Creates an empty Union-Find structure with a specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            payloads: HashMap::with_capacity(capacity),
+            sets: DenseUnionFind {
+                parent_links: Vec::with_capacity(capacity),
+                sizes: Vec::with_capacity(capacity),
+                count: 0,
+            },
+        }
+    }
+
+    /// Inserts a new item (disjoint set) into the data structure.
+    /// Does nothing if the item was inserted before.
+    pub fn insert(&mut self, item: T) {
+        if !self.payloads.contains_key(&item) {
+            let key = self.sets.push();
+            self.payloads.insert(item, key);
+        }
+    }
+
+    /// Returns the root index of the set containing the given value, or `None` if it doesn't exist.
+    pub fn find(&mut self, value: &T) -> Option<usize> {
+        let key = *self.payloads.get(value)?;
+        Some(self.sets.find(key))
+    }
+
+    /// Unites the sets containing the two given values. Returns:
+    /// - `None` if either value hasn't been inserted,
+    /// - `Some(true)` if two disjoint sets have been merged,
+    /// - `Some(false)` if both elements were already in the same set.
+    pub fn union(&mut self, first_item: &T, sec_item: &T) -> Option<bool> {
+        let first_key = *self.payloads.get(first_item)?;
+        let sec_key = *self.payloads.get(sec_item)?;
+        Some(self.sets.union(first_key, sec_key))
+    }
+
     /// Checks if two items belong to the same set.
     pub fn is_same_set(&mut self, first_item: &T, sec_item: &T) -> bool {
         matches!((self.find(first_item), self.find(sec_item)), (Some(first_root), Some(sec_root)) if first_root == sec_root)
@@ -96,17 +155,15 @@ impl<T: Debug + Eq + Hash> UnionFind<T> {
 
     /// Returns the number of disjoint sets.
     pub fn count(&self) -> usize {
-        self.count
+        self.sets.count()
     }
 }
 
 impl<T: Debug + Eq + Hash> Default for UnionFind<T> {
     fn default() -> Self {
         Self {
-            parent_links: Vec::default(),
-            sizes: Vec::default(),
             payloads: HashMap::default(),
-            count: 0,
+            sets: DenseUnionFind::default(),
         }
     }
 }
@@ -225,4 +282,47 @@ mod tests {
 
         assert_eq!(None, uf.union(&0, &10));
     }
+
+    #[test]
+    fn test_dense() {
+        let mut uf = DenseUnionFind::new(6);
+        assert_eq!((uf.len(), uf.count()), (6, 6));
+
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 1));
+        assert!(!uf.union(0, 2));
+        assert!(uf.union(4, 5));
+        assert!(uf.is_same_set(0, 2));
+        assert!(!uf.is_same_set(2, 4));
+        assert_eq!(uf.set_size(2), 3);
+        assert_eq!(uf.set_size(3), 1);
+        assert_eq!(uf.count(), 3);
+
+        let index = uf.push();
+        assert_eq!((index, uf.len(), uf.count()), (6, 7, 4));
+        assert!(uf.union(index, 3));
+        assert_eq!(uf.find(index), uf.find(3));
+        assert!(DenseUnionFind::default().is_empty());
+    }
+
+    #[test]
+    fn test_dense_large() {
+        // a million elements chained together one at a time
+        let n = 1_000_000;
+        let mut uf = DenseUnionFind::new(n);
+        for i in 1..n {
+            assert!(uf.union(i, i - 1));
+        }
+        assert_eq!(uf.count(), 1);
+        assert_eq!(uf.set_size(n - 1), n);
+        assert!(uf.is_same_set(0, n - 1));
+    }
+
+    #[test]
+    fn test_insert_twice() {
+        let mut uf = UnionFind::from_iter(["A", "B", "A"]);
+        assert_eq!(uf.count(), 2);
+        assert_eq!(uf.union(&"A", &"B"), Some(true));
+        assert_eq!(uf.count(), 1);
+    }
 }